@@ -7,7 +7,7 @@ fn criterion_benchmark(c: &mut Criterion) {
             let spea2_model = &mut mocks::get_spea2model();
             let mut model = spea2_model.get_model();
             let mut mutation = spea2_model.get_mutation_operator();
-            spea2::evolve(&mut model, &mut mutation)
+            spea2::evolve(&mut model, &mut mutation).unwrap()
         })
     });
 }
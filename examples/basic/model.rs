@@ -1,4 +1,5 @@
-use rand::{prelude::ThreadRng, Rng};
+use rand::Rng;
+use spea2::random::{self, SeededRng};
 use spea2::model::*;
 
 const ITEM_COUNT: usize = 20;
@@ -10,12 +11,12 @@ const MIN_WEIGHT: usize = 10;
 #[derive(Debug)]
 pub struct BasicModel {
     items: Vec<BasicModelItem>,
-    rnd: ThreadRng,
+    rnd: SeededRng,
 }
 
 impl BasicModel {
     pub fn new() -> Self {
-        let mut rnd = rand::thread_rng();
+        let mut rnd = random::get_rng();
         BasicModel {
             items: (0..ITEM_COUNT)
                 .map(|_| {
@@ -65,6 +66,7 @@ impl Spea2Model for BasicModel {
                 min: MIN_STRENGTH as f32,
                 max: MAX_STRENGTH as f32,
                 index: 0,
+                bounds_policy: BoundsPolicy::Ignore,
             },
             Objective {
                 name: "wieght".to_string(),
@@ -72,6 +74,7 @@ impl Spea2Model for BasicModel {
                 min: MIN_WEIGHT as f32,
                 max: MAX_WEIGHT as f32,
                 index: 1,
+                bounds_policy: BoundsPolicy::Ignore,
             },
         ];
         let population = self
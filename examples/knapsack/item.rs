@@ -30,7 +30,7 @@ impl ItemPool {
     }
 
     pub fn rnd() -> Item {
-        let mut rng = rand::thread_rng();
+        let mut rng = spea2::random::get_rng();
         Item::new(
             rng.gen_range(WEIGHT_MIN..WEIGHT_MAX),
             rng.gen_range(VALUE_MIN..VALUE_MAX),
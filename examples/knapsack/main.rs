@@ -4,12 +4,44 @@ mod sack;
 use sack::SackPool;
 use spea2::{canvas::Canvas, model::Spea2Model};
 
+const COMPARE_GENERATIONS: usize = 100;
+
 fn main() {
     let mut sack_pool = SackPool::new();
     sack_pool.fill();
 
+    if std::env::args().any(|arg| arg == "--compare-repair") {
+        compare_repair(&sack_pool);
+        return;
+    }
+
     let model = sack_pool.get_model();
     let mutation = sack_pool.get_mutation_operator();
 
     Canvas::new(model, mutation).show();
 }
+
+fn compare_repair(sack_pool: &SackPool) {
+    for repair in [true, false] {
+        let mut model = sack_pool.get_model();
+        let mut operators = sack_pool.get_operators(repair);
+        for _ in 0..COMPARE_GENERATIONS {
+            if let Err(error) = spea2::evolve_with(&mut model, &mut operators) {
+                println!("{}", error);
+                return;
+            }
+        }
+        let best_value = model
+            .archive
+            .iter()
+            .map(|model_item| model_item.values[0])
+            .fold(0.0, f32::max);
+        println!(
+            "repair {}: best value {:.1}, {} of {} archived sacks overweight",
+            if repair { "on" } else { "off" },
+            best_value,
+            sack_pool.count_overweight(&model.archive),
+            model.archive.len()
+        );
+    }
+}
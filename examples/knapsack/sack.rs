@@ -1,45 +1,21 @@
-use crate::item::{Item, ItemPool};
-use rand::{prelude::SliceRandom, Rng};
-use spea2::model::{Direction, Model, ModelItem, MutationOperator, Objective, Spea2Model};
+use crate::item::ItemPool;
+use rand::Rng;
+use spea2::model::{
+    BoundsPolicy, Direction, Genome, Model, ModelItem, MutationOperator, Objective, Operators,
+    Spea2Model, Variation,
+};
+use spea2::operators::{binary, with_evaluation, with_repair};
 
 const SACK_COUNT: usize = 10;
 const SACK_MAX_WEIGHT: f32 = 50.0;
 const SACK_MAX_VALUE: f32 = 50.0;
-
-#[derive(Debug, Clone, Default)]
-pub struct Sack {
-    pub weight: f32,
-    pub value: f32,
-    pub items: Vec<Item>,
-}
-impl Sack {
-    pub fn item_add(&mut self, item: Item) {
-        self.items.push(item);
-        self.weight += item.weight;
-        self.value += item.value;
-    }
-
-    pub fn item_remove(&mut self, index: usize) {
-        let item = self.items.swap_remove(index);
-        self.weight -= item.weight;
-        self.value -= item.value;
-    }
-
-    pub fn fill(&mut self, item_pool: &mut ItemPool) {
-        item_pool.items.shuffle(&mut rand::thread_rng());
-        for item in item_pool.items.iter() {
-            if self.weight + item.weight > SACK_MAX_WEIGHT {
-                continue;
-            }
-            self.item_add(*item);
-        }
-    }
-}
+const PACK_PROBABILITY: f64 = 0.2;
+const BIT_FLIP_PROBABILITY: f64 = 0.05;
 
 #[derive(Debug, Clone)]
 pub struct SackPool {
     pub item_pool: ItemPool,
-    pub sacks: Vec<Sack>,
+    pub sacks: Vec<Vec<bool>>,
 }
 
 impl SackPool {
@@ -50,13 +26,65 @@ impl SackPool {
             sacks: vec![],
         }
     }
+
     pub fn fill(&mut self) {
+        let mut rng = spea2::random::get_rng();
+        let (weights, priorities) = self.get_repair_parameters();
         for _ in 0..SACK_COUNT {
-            let mut sack = Sack::default();
-            sack.fill(&mut self.item_pool);
+            let mut sack: Vec<bool> = (0..self.item_pool.items.len())
+                .map(|_| rng.gen_bool(PACK_PROBABILITY))
+                .collect();
+            binary::greedy_repair(&mut sack, &weights, &priorities, SACK_MAX_WEIGHT as f64);
             self.sacks.push(sack);
         }
     }
+
+    /// Operators for `evolve_with`, with the overweight repair toggled by `repair` so the two can
+    /// be compared.
+    pub fn get_operators(&self, repair: bool) -> Operators<'_> {
+        let (weights, priorities) = self.get_repair_parameters();
+        let item_pool = &self.item_pool;
+        Operators {
+            variation: Variation::Genetic,
+            crossover: Some(binary::get_uniform_crossover_operator()),
+            mutation: binary::get_bit_flip_mutation_operator(BIT_FLIP_PROBABILITY),
+            portfolio: None,
+            repair: repair.then(|| {
+                binary::get_greedy_repair_operator(weights, priorities, SACK_MAX_WEIGHT as f64)
+            }),
+            evaluation: Some(Box::new(move |_, model_item| {
+                evaluate(item_pool, model_item)
+            })),
+        }
+    }
+
+    pub fn count_overweight(&self, model_items: &[ModelItem]) -> usize {
+        model_items
+            .iter()
+            .filter(|model_item| model_item.values[1] > SACK_MAX_WEIGHT)
+            .count()
+    }
+
+    fn get_repair_parameters(&self) -> (Vec<f64>, Vec<f64>) {
+        self.item_pool
+            .items
+            .iter()
+            .map(|item| (item.weight as f64, (item.value / item.weight) as f64))
+            .unzip()
+    }
+}
+
+fn evaluate(item_pool: &ItemPool, model_item: &mut ModelItem) {
+    if let Genome::Binary(sack) = &model_item.genome {
+        let (value, weight) = sack
+            .iter()
+            .zip(item_pool.items.iter())
+            .filter(|(packed, _)| **packed)
+            .fold((0.0, 0.0), |(value, weight), (_, item)| {
+                (value + item.value, weight + item.weight)
+            });
+        model_item.values = vec![value, weight];
+    }
 }
 
 impl Spea2Model for SackPool {
@@ -68,6 +96,7 @@ impl Spea2Model for SackPool {
                 min: 0.0,
                 max: SACK_MAX_VALUE,
                 index: 0,
+                bounds_policy: BoundsPolicy::Ignore,
             },
             Objective {
                 name: "sack wieght".to_string(),
@@ -75,70 +104,60 @@ impl Spea2Model for SackPool {
                 min: 0.0,
                 max: SACK_MAX_WEIGHT,
                 index: 1,
+                bounds_policy: BoundsPolicy::Ignore,
             },
         ];
         let population = self
             .sacks
             .iter()
-            .enumerate()
-            .map(|(index, sack)| ModelItem::new(vec![sack.value, sack.weight], Some(index)))
+            .map(|sack| {
+                let mut model_item = ModelItem::new(vec![], None);
+                model_item.genome = Genome::Binary(sack.clone());
+                evaluate(&self.item_pool, &mut model_item);
+                model_item
+            })
             .collect();
         Model::new(objectives, population)
     }
 
     fn get_mutation_operator(&mut self) -> MutationOperator<'_> {
-        let mut_op = move |_: &[Objective], model_item: &mut ModelItem| {
-            let mut rng = rand::thread_rng();
-            let sack_index = model_item.custom_data_index.unwrap();
-            let sack = self.sacks.get_mut(sack_index).unwrap();
-            let item_index = rng.gen_range(0..sack.items.len());
-            sack.item_remove(item_index);
-            sack.fill(&mut self.item_pool);
-            model_item.values = vec![sack.value, sack.weight];
-        };
-        Box::new(mut_op)
+        let (weights, priorities) = self.get_repair_parameters();
+        let mutation = with_repair(
+            binary::get_bit_flip_mutation_operator(BIT_FLIP_PROBABILITY),
+            binary::get_greedy_repair_operator(weights, priorities, SACK_MAX_WEIGHT as f64),
+        );
+        let item_pool = &self.item_pool;
+        with_evaluation(
+            mutation,
+            Box::new(move |_, model_item| evaluate(item_pool, model_item)),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn sack_item_add() {
-        let mut sack = Sack::default();
-        sack.item_add(Item::new(1.0, 3.0));
-        sack.item_add(Item::new(2.0, 2.0));
-        sack.item_add(Item::new(3.0, 1.0));
-
-        assert_eq!(sack.weight, 6.0);
-        assert_eq!(sack.value, 6.0);
+    use crate::item::Item;
+
+    fn get_item_pool() -> ItemPool {
+        ItemPool {
+            items: vec![
+                Item::new(1.0, 3.0),
+                Item::new(2.0, 2.0),
+                Item::new(3.0, 1.0),
+            ],
+        }
     }
 
     #[test]
-    fn sack_item_remove() {
-        let mut sack = Sack::default();
-        sack.item_add(Item::new(1.0, 3.0));
-        sack.item_add(Item::new(2.0, 2.0));
-        sack.item_add(Item::new(3.0, 1.0));
-
-        assert_eq!(sack.weight, 6.0);
-        assert_eq!(sack.value, 6.0);
+    fn sack_evaluate() {
+        let item_pool = get_item_pool();
+        let mut model_item = ModelItem::new(vec![], None);
+        model_item.genome = Genome::Binary(vec![true, false, true]);
 
-        sack.item_remove(2);
+        evaluate(&item_pool, &mut model_item);
 
-        assert_eq!(sack.weight, 3.0);
-        assert_eq!(sack.value, 5.0);
-    }
-
-    #[test]
-    fn sack_fill() {
-        let mut sack = Sack::default();
-        let mut item_pool = ItemPool::new();
-        sack.fill(&mut item_pool);
-        assert!(sack.weight <= SACK_MAX_WEIGHT);
-        assert!(sack.value > 0.0);
-        assert!(!sack.items.is_empty());
+        assert_eq!(model_item.values, [4.0, 4.0]);
     }
 
     #[test]
@@ -147,7 +166,12 @@ mod tests {
         assert!(!sack_pool.item_pool.items.is_empty());
 
         sack_pool.fill();
-        assert!(!sack_pool.sacks.is_empty());
+        assert_eq!(sack_pool.sacks.len(), SACK_COUNT);
+
+        let model = sack_pool.get_model();
+        model.population.iter().for_each(|model_item| {
+            assert!(model_item.values[1] <= SACK_MAX_WEIGHT);
+        });
     }
 
     #[test]
@@ -157,13 +181,33 @@ mod tests {
         let mut model = sack_pool.get_model();
         let mut mutatation = sack_pool.get_mutation_operator();
         let model_item = model.population.get_mut(0).unwrap();
-        let before = model_item.values.clone();
+        let before = model_item.genome.clone();
+
+        (0..100).for_each(|_| {
+            mutatation(&model.objectives, model_item);
+            assert!(model_item.values[1] <= SACK_MAX_WEIGHT);
+        });
+
+        assert!(model_item.genome != before);
+
+        drop(mutatation);
+        let mut expected = model_item.clone();
+        evaluate(&sack_pool.item_pool, &mut expected);
+        assert_eq!(model_item.values, expected.values);
+    }
 
-        mutatation(&model.objectives, model_item);
+    #[test]
+    fn sack_pool_operators_with_repair() {
+        let mut sack_pool = SackPool::new();
+        sack_pool.fill();
+        let mut model = sack_pool.get_model();
+        let mut operators = sack_pool.get_operators(true);
 
-        assert!(model_item.values != before);
+        (0..20).for_each(|_| {
+            spea2::evolve_with(&mut model, &mut operators).unwrap();
+        });
 
-        println!("before: {:?}", before);
-        println!("after: {:?}", model_item.values);
+        assert_eq!(sack_pool.count_overweight(&model.population), 0);
+        assert_eq!(sack_pool.count_overweight(&model.archive), 0);
     }
 }
@@ -0,0 +1,336 @@
+use std::error::Error;
+
+use plotters::prelude::*;
+
+use crate::model::{Direction, ModelItem, Objective};
+
+/// The best (1), median and worst (all runs) attainment surfaces of a set of runs.
+#[derive(Debug, Default, Clone)]
+pub struct AttainmentSurfaces {
+    pub best: Vec<Vec<f32>>,
+    pub median: Vec<Vec<f32>>,
+    pub worst: Vec<Vec<f32>>,
+}
+
+impl AttainmentSurfaces {
+    pub fn new(objectives: &[Objective], archives: &[Vec<ModelItem>]) -> Self {
+        let runs = archives.len();
+        Self {
+            best: attainment_surface(objectives, archives, 1),
+            median: attainment_surface(objectives, archives, runs.div_ceil(2)),
+            worst: attainment_surface(objectives, archives, runs),
+        }
+    }
+}
+
+/// The k%-attainment surface of the final `archives` of independent runs, where k is `k / runs`:
+/// the minimal points of the region of objective space weakly dominated by the items of at least
+/// `k` archives. A point is attained by `k` runs if it is weakly dominated by a surface point.
+///
+/// Supports 2 or 3 objectives; points are sorted along the first objective from best to worst.
+pub fn attainment_surface(
+    objectives: &[Objective],
+    archives: &[Vec<ModelItem>],
+    k: usize,
+) -> Vec<Vec<f32>> {
+    assert!(
+        objectives.len() == 2 || objectives.len() == 3,
+        "attainment surfaces need 2 or 3 objectives"
+    );
+    if k == 0 || k > archives.len() {
+        return vec![];
+    }
+    let runs: Vec<Vec<Vec<f32>>> = archives
+        .iter()
+        .map(|archive| {
+            archive
+                .iter()
+                .map(|item| get_minimised(objectives, &item.values))
+                .collect()
+        })
+        .collect();
+
+    let surface = match objectives.len() {
+        2 => get_surface_2d(&runs, k),
+        _ => get_surface_3d(&runs, k),
+    };
+    surface
+        .into_iter()
+        .map(|point| get_minimised(objectives, &point))
+        .collect()
+}
+
+/// Negates maximised objectives, so that lower is better in every objective. Its own inverse.
+fn get_minimised(objectives: &[Objective], values: &[f32]) -> Vec<f32> {
+    objectives
+        .iter()
+        .map(|objective| match objective.direction {
+            Direction::Maximised => -values[objective.index],
+            Direction::Minimised => values[objective.index],
+        })
+        .collect()
+}
+
+/// Sweeps the first objective: at each value, every run's best second objective so far is known,
+/// and the k-th smallest of those is the surface level.
+fn get_surface_2d(runs: &[Vec<Vec<f32>>], k: usize) -> Vec<Vec<f32>> {
+    let mut points: Vec<(usize, &Vec<f32>)> = runs
+        .iter()
+        .enumerate()
+        .flat_map(|(run, points)| points.iter().map(move |point| (run, point)))
+        .collect();
+    points.sort_by(|(_, a), (_, b)| a[0].total_cmp(&b[0]));
+
+    let mut best = vec![f32::INFINITY; runs.len()];
+    let mut surface: Vec<Vec<f32>> = vec![];
+    let mut level = f32::INFINITY;
+    for (i, (run, point)) in points.iter().enumerate() {
+        best[*run] = best[*run].min(point[1]);
+        if points
+            .get(i + 1)
+            .is_some_and(|(_, next)| next[0] == point[0])
+        {
+            continue;
+        }
+        let mut sorted = best.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        if sorted[k - 1] < level {
+            level = sorted[k - 1];
+            surface.push(vec![point[0], level]);
+        }
+    }
+    surface
+}
+
+/// Sweeps the third objective, adding the points of each 2D surface that were not already
+/// attained at a lower level.
+fn get_surface_3d(runs: &[Vec<Vec<f32>>], k: usize) -> Vec<Vec<f32>> {
+    let mut levels: Vec<f32> = runs.iter().flatten().map(|point| point[2]).collect();
+    levels.sort_by(|a, b| a.total_cmp(b));
+    levels.dedup();
+
+    let mut surface: Vec<Vec<f32>> = vec![];
+    let mut previous: Vec<Vec<f32>> = vec![];
+    for level in levels {
+        let slice: Vec<Vec<Vec<f32>>> = runs
+            .iter()
+            .map(|points| {
+                points
+                    .iter()
+                    .filter(|point| point[2] <= level)
+                    .map(|point| point[..2].to_vec())
+                    .collect()
+            })
+            .collect();
+        let current = get_surface_2d(&slice, k);
+        current
+            .iter()
+            .filter(|point| {
+                !previous
+                    .iter()
+                    .any(|other| other[0] <= point[0] && other[1] <= point[1])
+            })
+            .for_each(|point| surface.push(vec![point[0], point[1], level]));
+        previous = current;
+    }
+    surface
+}
+
+/// Draws 2-objective attainment surfaces as staircases in an SVG figure, bounded by the
+/// objectives' `min..=max`.
+pub fn draw_attainment_surfaces(
+    objectives: &[Objective],
+    surfaces: &AttainmentSurfaces,
+    path: &str,
+) -> Result<(), Box<dyn Error>> {
+    assert_eq!(objectives.len(), 2, "only 2 objectives can be drawn");
+    let root = SVGBackend::new(path, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Attainment surfaces", ("sans-serif", 24))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(
+            objectives[0].min..objectives[0].max,
+            objectives[1].min..objectives[1].max,
+        )?;
+    chart
+        .configure_mesh()
+        .x_desc(objectives[0].name.as_str())
+        .y_desc(objectives[1].name.as_str())
+        .draw()?;
+
+    for (surface, label, colour) in [
+        (&surfaces.best, "best", GREEN),
+        (&surfaces.median, "median", BLUE),
+        (&surfaces.worst, "worst", RED),
+    ] {
+        chart
+            .draw_series(LineSeries::new(get_staircase(objectives, surface), &colour))?
+            .label(label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], colour));
+    }
+    chart
+        .configure_series_labels()
+        .background_style(WHITE)
+        .border_style(BLACK)
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
+
+/// The outline of the region a 2D surface attains, running out to the worst objective bounds.
+fn get_staircase(objectives: &[Objective], surface: &[Vec<f32>]) -> Vec<(f32, f32)> {
+    let worst = get_minimised(
+        objectives,
+        &objectives
+            .iter()
+            .map(|objective| match objective.direction {
+                Direction::Maximised => objective.min,
+                Direction::Minimised => objective.max,
+            })
+            .collect::<Vec<_>>(),
+    );
+    let surface: Vec<Vec<f32>> = surface
+        .iter()
+        .map(|point| get_minimised(objectives, point))
+        .collect();
+
+    let mut staircase: Vec<Vec<f32>> = vec![];
+    if let Some(first) = surface.first() {
+        staircase.push(vec![first[0], worst[1]]);
+    }
+    for (i, point) in surface.iter().enumerate() {
+        staircase.push(point.clone());
+        let next_x = surface.get(i + 1).map_or(worst[0], |next| next[0]);
+        staircase.push(vec![next_x, point[1]]);
+    }
+    staircase
+        .into_iter()
+        .map(|point| {
+            let point = get_minimised(objectives, &point);
+            (point[0], point[1])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::BoundsPolicy;
+
+    fn get_objectives(count: usize) -> Vec<Objective> {
+        (0..count)
+            .map(|index| Objective {
+                name: format!("f{}", index),
+                direction: Direction::Minimised,
+                min: 0.0,
+                max: 10.0,
+                index,
+                bounds_policy: BoundsPolicy::Ignore,
+            })
+            .collect()
+    }
+
+    fn get_archive(values: &[&[f32]]) -> Vec<ModelItem> {
+        values
+            .iter()
+            .map(|values| ModelItem::new(values.to_vec(), None))
+            .collect()
+    }
+
+    fn is_attained(surface: &[Vec<f32>], point: &[f32]) -> bool {
+        surface
+            .iter()
+            .any(|other| other.iter().zip(point).all(|(a, b)| a <= b))
+    }
+
+    #[test]
+    fn attainment_surface_2d() {
+        let objectives = get_objectives(2);
+        let archives = vec![
+            get_archive(&[&[1.0, 5.0], &[3.0, 2.0]]),
+            get_archive(&[&[2.0, 4.0], &[4.0, 1.0]]),
+            get_archive(&[&[2.0, 6.0], &[5.0, 3.0]]),
+        ];
+
+        let surfaces = AttainmentSurfaces::new(&objectives, &archives);
+
+        assert_eq!(
+            surfaces.best,
+            [[1.0, 5.0], [2.0, 4.0], [3.0, 2.0], [4.0, 1.0]]
+        );
+        assert_eq!(surfaces.median, [[2.0, 5.0], [3.0, 4.0], [4.0, 2.0]]);
+        assert_eq!(surfaces.worst, [[2.0, 6.0], [5.0, 3.0]]);
+    }
+
+    #[test]
+    fn attainment_surface_direction() {
+        let mut objectives = get_objectives(2);
+        objectives[0].direction = Direction::Maximised;
+        let archives = vec![
+            get_archive(&[&[9.0, 5.0], &[7.0, 2.0]]),
+            get_archive(&[&[8.0, 1.0]]),
+        ];
+
+        let surface = attainment_surface(&objectives, &archives, 2);
+
+        assert_eq!(surface, [[8.0, 5.0], [7.0, 2.0]]);
+    }
+
+    #[test]
+    fn attainment_surface_3d() {
+        let objectives = get_objectives(3);
+        let archives = vec![
+            get_archive(&[&[1.0, 4.0, 2.0], &[3.0, 1.0, 3.0], &[2.0, 2.0, 1.0]]),
+            get_archive(&[&[2.0, 3.0, 3.0], &[4.0, 2.0, 1.0]]),
+            get_archive(&[&[1.0, 1.0, 4.0], &[3.0, 3.0, 2.0]]),
+        ];
+        let runs: Vec<Vec<Vec<f32>>> = archives
+            .iter()
+            .map(|archive| archive.iter().map(|item| item.values.clone()).collect())
+            .collect();
+
+        for k in 1..=3 {
+            let surface = attainment_surface(&objectives, &archives, k);
+            for x in 0..=5 {
+                for y in 0..=5 {
+                    for z in 0..=5 {
+                        let point = [x as f32, y as f32, z as f32];
+                        let count = runs.iter().filter(|run| is_attained(run, &point)).count();
+                        assert_eq!(is_attained(&surface, &point), count >= k, "{:?}", point);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn attainment_draw_surfaces() {
+        let objectives = get_objectives(2);
+        let archives = vec![
+            get_archive(&[&[1.0, 5.0], &[3.0, 2.0]]),
+            get_archive(&[&[2.0, 4.0], &[4.0, 1.0]]),
+        ];
+        let surfaces = AttainmentSurfaces::new(&objectives, &archives);
+        let path = std::env::temp_dir().join("spea2_attainment_draw_surfaces.svg");
+
+        draw_attainment_surfaces(&objectives, &surfaces, path.to_str().unwrap()).unwrap();
+
+        assert!(std::fs::read_to_string(&path).unwrap().contains("<svg"));
+        assert_eq!(
+            get_staircase(&objectives, &surfaces.worst),
+            [
+                (2.0, 10.0),
+                (2.0, 5.0),
+                (3.0, 5.0),
+                (3.0, 4.0),
+                (4.0, 4.0),
+                (4.0, 2.0),
+                (10.0, 2.0)
+            ]
+        );
+    }
+}
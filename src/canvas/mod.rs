@@ -85,6 +85,8 @@ impl<'a> Canvas<'a> {
 
     fn update(&mut self) {
         println!("{:?}", "update");
-        super::evolve(&mut self.model, &mut self.mutation)
+        if let Err(error) = super::evolve(&mut self.model, &mut self.mutation) {
+            println!("{}", error);
+        }
     }
 }
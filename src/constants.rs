@@ -1 +1,3 @@
 pub const MUTATION_RATE: f64 = 0.1;
+/// How many times a duplicate is replaced while the replacement is still a duplicate.
+pub const DUPLICATE_REPLACEMENT_ATTEMPTS: usize = 10;
@@ -1,11 +1,12 @@
-use crate::model::{Model, ModelItem};
+use crate::model::{CrossoverOperator, Model, ModelItem};
 extern crate itermore;
+use crate::random::{self, SeededRng};
 use itermore::Itermore;
-use rand::prelude::{SliceRandom, ThreadRng};
+use rand::prelude::SliceRandom;
 use rand::Rng;
 
 pub fn neighbourhood_crossover(model: &mut Model) {
-    let mut rng = rand::thread_rng();
+    let mut rng = random::get_rng();
     let split_index = rng.gen_range(0..model.objectives.len()) as usize;
 
     sort_pool_by_objective(model);
@@ -16,14 +17,35 @@ pub fn neighbourhood_crossover(model: &mut Model) {
     }
 }
 
+/// Pairs up neighbours in objective space as `neighbourhood_crossover` does, returning how many
+/// items at the front of the mating pool were crossed.
+pub fn apply_crossover(model: &mut Model, crossover: &mut CrossoverOperator) -> usize {
+    pair_neighbours(model);
+
+    let objectives = &model.objectives;
+    let mut crossed = 0;
+    for [p1, p2] in model.mating_pool.iter_mut().array_chunks() {
+        crossover(objectives, p1, p2);
+        crossed += 2;
+    }
+    crossed
+}
+
+/// Orders the mating pool so that consecutive pairs are neighbours in objective space.
+pub fn pair_neighbours(model: &mut Model) {
+    let mut rng = random::get_rng();
+    sort_pool_by_objective(model);
+    neighbourhood_shuffle(model, &mut rng);
+}
+
 fn sort_pool_by_objective(model: &mut Model) {
     let i = model.next_objective_sort_index();
     model
         .mating_pool
-        .sort_by(|a, b| a.values[i].partial_cmp(&b.values[i]).unwrap());
+        .sort_by(|a, b| a.values[i].total_cmp(&b.values[i]));
 }
 
-fn neighbourhood_shuffle(model: &'_ mut Model, rng: &mut ThreadRng) {
+fn neighbourhood_shuffle(model: &'_ mut Model, rng: &mut SeededRng) {
     let ns = model.neighbourhood_size;
     let pool = model.mating_pool.as_mut_slice();
     for i in 0..pool.len() / ns {
@@ -51,7 +73,7 @@ mod tests {
 
     #[test]
     fn crossover_sort_pool_by_objective() {
-        let mut rng = rand::thread_rng();
+        let mut rng = random::get_rng();
         let mut model = mocks::get_model_with_mating_pool();
         model.mating_pool.shuffle(&mut rng);
 
@@ -69,7 +91,7 @@ mod tests {
     #[test]
     fn crossover_neighbourhood_shuffle() {
         let mut model = mocks::get_model_with_mating_pool();
-        let mut rng = rand::thread_rng();
+        let mut rng = random::get_rng();
         let sort_index = 0;
 
         neighbourhood_shuffle(&mut model, &mut rng);
@@ -88,6 +110,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn crossover_apply_crossover() {
+        let mut model = mocks::get_model_with_mating_pool();
+        let mut crossover: CrossoverOperator = Box::new(|_, p1, p2| {
+            p1.fitness += 1.0;
+            p2.fitness += 1.0;
+        });
+
+        let crossed = apply_crossover(&mut model, &mut crossover);
+
+        assert_eq!(crossed, model.mating_pool.len());
+        assert!(model.mating_pool.iter().all(|item| item.fitness == 1.0));
+    }
+
     #[test]
     fn crossover_perform_crossover() {
         let model = mocks::get_model_with_mating_pool();
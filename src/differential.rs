@@ -0,0 +1,175 @@
+use rand::{prelude::SliceRandom, Rng};
+
+use crate::model::{Genome, Model, ModelItem};
+use crate::operators::real::Bounds;
+use crate::random;
+
+/// Fills the mating pool with DE/rand/1/bin trial vectors: each archive member in turn is the
+/// target, crossed with `r1 + f * (r2 - r3)` for three other randomly chosen archive members.
+/// Archives with fewer than four members draw the donors with replacement, and a single member is
+/// copied into the whole mating pool. Returns whether each trial differs from its target; targets
+/// without a real genome, with an empty one or with donors of another length are copied unchanged,
+/// keeping their samples.
+pub fn differential_evolution(model: &mut Model, f: f64, cr: f64, bounds: &[Bounds]) -> Vec<bool> {
+    let mut rng = random::get_rng();
+    let archive = &model.archive;
+    let mut changed = vec![];
+    model.mating_pool.clear();
+    if archive.len() < 2 {
+        model.mating_pool = archive
+            .iter()
+            .cycle()
+            .take(model.population_size)
+            .cloned()
+            .collect();
+        changed.resize(model.mating_pool.len(), false);
+        return changed;
+    }
+
+    for i in 0..model.population_size {
+        let target_index = i % archive.len();
+        let others: Vec<usize> = (0..archive.len()).filter(|&j| j != target_index).collect();
+        let donors: Vec<&ModelItem> = match others.len() {
+            len if len < 3 => (0..3)
+                .map(|_| &archive[others[rng.gen_range(0..len)]])
+                .collect(),
+            _ => others
+                .choose_multiple(&mut rng, 3)
+                .map(|&j| &archive[j])
+                .collect(),
+        };
+
+        let target = &archive[target_index];
+        let mut trial = target.clone();
+        if let (
+            Genome::Real(x),
+            Genome::Real(r1),
+            Genome::Real(r2),
+            Genome::Real(r3),
+            Genome::Real(y),
+        ) = (
+            &target.genome,
+            &donors[0].genome,
+            &donors[1].genome,
+            &donors[2].genome,
+            &mut trial.genome,
+        ) {
+            let len = x.len();
+            if len > 0 && [r1, r2, r3].iter().all(|r| r.len() == len) {
+                let j_rand = rng.gen_range(0..len);
+                for k in 0..len {
+                    if k == j_rand || rng.gen_bool(cr) {
+                        let mutant = r1[k] + f * (r2[k] - r3[k]);
+                        y[k] = match bounds.get(k) {
+                            Some(bound) => mutant.clamp(bound.min, bound.max),
+                            None => mutant,
+                        };
+                    }
+                }
+            }
+        }
+        let is_changed = trial.genome != target.genome;
+        if is_changed {
+            trial.reset_samples();
+        }
+        changed.push(is_changed);
+        model.mating_pool.push(trial);
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{self, MOCK_POPULATION_COUNT};
+
+    fn get_model() -> Model {
+        let mut model = mocks::get_model_with_real_genomes();
+        model.archive = model.population.drain(..).collect();
+        model
+    }
+
+    fn get_genes(item: &ModelItem) -> &Vec<f64> {
+        match &item.genome {
+            Genome::Real(x) => x,
+            _ => panic!("expected a real genome"),
+        }
+    }
+
+    #[test]
+    fn differential_evolution_in_bounds() {
+        let mut model = get_model();
+        let bounds = mocks::get_real_bounds();
+
+        differential_evolution(&mut model, 2.0, 0.9, &bounds);
+
+        assert_eq!(model.mating_pool.len(), MOCK_POPULATION_COUNT);
+        model.mating_pool.iter().for_each(|item| {
+            get_genes(item)
+                .iter()
+                .zip(bounds.iter())
+                .for_each(|(x, bound)| assert!(*x >= bound.min && *x <= bound.max));
+        });
+    }
+
+    #[test]
+    fn differential_evolution_crossover_rate() {
+        let mut model = get_model();
+
+        differential_evolution(&mut model, 0.5, 0.0, &mocks::get_real_bounds());
+
+        model
+            .mating_pool
+            .iter()
+            .zip(model.archive.iter())
+            .for_each(|(trial, target)| {
+                let changed = get_genes(trial)
+                    .iter()
+                    .zip(get_genes(target).iter())
+                    .filter(|(a, b)| a != b)
+                    .count();
+                assert!(changed <= 1);
+            });
+    }
+
+    #[test]
+    fn differential_evolution_small_archive() {
+        let mut model = get_model();
+        model.archive.truncate(3);
+
+        differential_evolution(&mut model, 0.5, 0.9, &mocks::get_real_bounds());
+
+        assert_eq!(model.mating_pool.len(), MOCK_POPULATION_COUNT);
+    }
+
+    #[test]
+    fn differential_evolution_single_member() {
+        let mut model = get_model();
+        model.archive.truncate(1);
+
+        let changed = differential_evolution(&mut model, 0.5, 0.9, &mocks::get_real_bounds());
+
+        assert_eq!(changed.len(), MOCK_POPULATION_COUNT);
+        assert_eq!(model.mating_pool.len(), MOCK_POPULATION_COUNT);
+        assert!(changed.iter().all(|changed| !changed));
+        assert!(model
+            .mating_pool
+            .iter()
+            .all(|item| item.genome == model.archive[0].genome));
+    }
+
+    #[test]
+    fn differential_evolution_mismatched_genomes() {
+        let mut model = get_model();
+        model.archive[0].genome = Genome::Real(vec![]);
+        model.archive[0].samples = 3;
+        model.archive[1].genome = Genome::Real(vec![0.5]);
+
+        let changed = differential_evolution(&mut model, 0.5, 0.9, &mocks::get_real_bounds());
+
+        assert!(!changed[0]);
+        assert_eq!(model.mating_pool[0].genome, Genome::Real(vec![]));
+        assert_eq!(model.mating_pool[0].samples, 3);
+        assert_eq!(model.mating_pool.len(), MOCK_POPULATION_COUNT);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::constants::DUPLICATE_REPLACEMENT_ATTEMPTS;
+use crate::model::{DuplicateDetection, Model, ModelItem, Objective};
+
+/// Replaces archive and population members that duplicate an earlier one, retrying while the
+/// replacement is itself a duplicate. For `DuplicateDetection::GenomeHash`, items with a genome
+/// have their `genome_hash` recomputed first, so hashes carried over from a parent are not trusted.
+pub fn eliminate_duplicates(
+    model: &mut Model,
+    mut replace: impl FnMut(&[Objective], &mut ModelItem),
+) -> usize {
+    let detection = &model.duplicate_detection;
+    if let DuplicateDetection::Disabled = detection {
+        return 0;
+    }
+
+    let objectives = &model.objectives;
+    let mut seen: Vec<ModelItem> = vec![];
+    let mut replaced = 0;
+    let mut replacements = 0;
+    model
+        .archive
+        .iter_mut()
+        .chain(model.population.iter_mut())
+        .for_each(|item| {
+            let is_seen = |item: &mut ModelItem| {
+                if let DuplicateDetection::GenomeHash = detection {
+                    item.genome_hash = item.genome.get_hash().or(item.genome_hash);
+                }
+                seen.iter()
+                    .any(|other| is_duplicate(detection, other, item))
+            };
+            if is_seen(item) {
+                replaced += 1;
+                for _ in 0..DUPLICATE_REPLACEMENT_ATTEMPTS {
+                    replace(objectives, item);
+                    item.reset_samples();
+                    replacements += 1;
+                    if !is_seen(item) {
+                        break;
+                    }
+                }
+            }
+            seen.push(item.clone());
+        });
+    model.evaluations += replacements;
+    replaced
+}
+
+fn is_duplicate(detection: &DuplicateDetection, a: &ModelItem, b: &ModelItem) -> bool {
+    match *detection {
+        DuplicateDetection::Disabled => false,
+        DuplicateDetection::Exact => a.values == b.values,
+        DuplicateDetection::Tolerance(tolerance) => a
+            .values
+            .iter()
+            .zip(b.values.iter())
+            .all(|(a, b)| (a - b).abs() <= tolerance),
+        DuplicateDetection::GenomeHash => a.genome_hash.is_some() && a.genome_hash == b.genome_hash,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks;
+    use crate::model::{Genome, MutationOperator, Spea2Model};
+
+    #[test]
+    fn duplicates_is_duplicate() {
+        let a = ModelItem::new(vec![1.0, 2.0], Some(0));
+        let mut b = ModelItem::new(vec![1.0, 2.05], Some(1));
+
+        assert!(!is_duplicate(&DuplicateDetection::Exact, &a, &b));
+        assert!(is_duplicate(&DuplicateDetection::Tolerance(0.1), &a, &b));
+        assert!(!is_duplicate(&DuplicateDetection::Tolerance(0.01), &a, &b));
+        assert!(!is_duplicate(&DuplicateDetection::GenomeHash, &a, &b));
+
+        b.values[1] = 2.0;
+        assert!(is_duplicate(&DuplicateDetection::Exact, &a, &b));
+        assert!(!is_duplicate(&DuplicateDetection::Disabled, &a, &b));
+
+        let mut c = a.clone();
+        c.genome_hash = Some(7);
+        b.genome_hash = Some(7);
+        assert!(is_duplicate(&DuplicateDetection::GenomeHash, &c, &b));
+    }
+
+    #[test]
+    fn duplicates_eliminate_duplicates() {
+        let mut model = mocks::get_model_basic();
+        let mut duplicate = model.archive[0].clone();
+        duplicate.genome = Genome::Real(vec![1.0]);
+        model.population.push(duplicate);
+        model.population.push(model.population[0].clone());
+        let mut replacement: MutationOperator = Box::new(|_, item| item.values[0] += 100.0);
+
+        assert_eq!(eliminate_duplicates(&mut model, &mut replacement), 0);
+
+        model.duplicate_detection = DuplicateDetection::Exact;
+        assert_eq!(eliminate_duplicates(&mut model, &mut replacement), 2);
+        assert_eq!(model.population[2].values, [100.0, 3.0]);
+        assert_eq!(model.population[3].values, [100.0, 0.0]);
+        assert_eq!(eliminate_duplicates(&mut model, &mut replacement), 0);
+    }
+
+    #[test]
+    fn duplicates_evolve_archive_has_no_duplicates() {
+        crate::random::seed(3);
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = spea2_model.get_model();
+        let mut mutation = spea2_model.get_mutation_operator();
+        model.duplicate_detection = DuplicateDetection::Exact;
+
+        for _ in 0..5 {
+            crate::evolve(&mut model, &mut mutation).unwrap();
+            for (i, a) in model.archive.iter().enumerate() {
+                assert!(model.archive[i + 1..].iter().all(|b| a.values != b.values));
+            }
+        }
+    }
+
+    #[test]
+    fn duplicates_genome_hash_from_genome() {
+        let mut model = mocks::get_model_with_real_genomes();
+        model.archive.clear();
+        model.population.truncate(1);
+        model.population[0].genome_hash = Some(7);
+        let mut clone = model.population[0].clone();
+        clone.values[0] += 1.0;
+        clone.genome_hash = Some(8);
+        model.population.push(clone);
+        model.duplicate_detection = DuplicateDetection::GenomeHash;
+        let mut replacement: MutationOperator = Box::new(|_, item| {
+            if let Genome::Real(genes) = &mut item.genome {
+                genes[0] += 1.0;
+            }
+        });
+
+        assert_eq!(eliminate_duplicates(&mut model, &mut replacement), 1);
+        assert_eq!(
+            model.population[1].genome_hash,
+            model.population[1].genome.get_hash()
+        );
+        assert_ne!(
+            model.population[0].genome_hash,
+            model.population[1].genome_hash
+        );
+    }
+
+    #[test]
+    fn duplicates_recheck_replacement() {
+        let mut model = mocks::get_model_basic();
+        model.archive.clear();
+        model.population = vec![
+            ModelItem::new(vec![0.0, 0.0], None),
+            ModelItem::new(vec![1.0, 0.0], None),
+            ModelItem::new(vec![0.0, 0.0], None),
+        ];
+        model.population[2].samples = 3;
+        model.duplicate_detection = DuplicateDetection::Exact;
+        let mut replacement: MutationOperator = Box::new(|_, item| item.values[0] += 1.0);
+
+        assert_eq!(eliminate_duplicates(&mut model, &mut replacement), 1);
+        assert_eq!(model.population[2].values, [2.0, 0.0]);
+        assert_eq!(model.population[2].samples, 1);
+        assert_eq!(model.evaluations, 2);
+    }
+
+    #[test]
+    fn duplicates_eliminate_duplicates_with_random() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = mocks::get_model_basic();
+        model.population.push(model.population[1].clone());
+        model.duplicate_detection = DuplicateDetection::Exact;
+        let custom_data_len = spea2_model.custom_data.len();
+
+        assert_eq!(
+            crate::eliminate_duplicates_with_random(&mut model, &mut spea2_model),
+            1
+        );
+        assert_eq!(model.population[2].custom_data_index, Some(custom_data_len));
+    }
+}
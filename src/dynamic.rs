@@ -0,0 +1,214 @@
+use crate::error::Spea2Error;
+use crate::evolve;
+use crate::model::{Model, MutationSchedule, Spea2Model};
+
+#[derive(Debug, Clone)]
+pub enum ChangeResponse {
+    ReEvaluate,
+    /// Re-evaluate, then replace this proportion of the population with `Spea2Model::get_random_item`.
+    RandomImmigrants(f64),
+    /// Re-evaluate, then mutate at `rate` for the next `generations` generations.
+    Hypermutation {
+        rate: f64,
+        generations: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct DynamicConfig {
+    pub sentinel_count: usize,
+    pub tolerance: f32,
+    pub response: ChangeResponse,
+}
+
+/// Evolves `model` while watching for changes in the environment, returning the generations at
+/// which a change was detected and responded to. A hypermutation still running when the call
+/// returns is cut short, restoring the original mutation schedule.
+pub fn evolve_dynamic<S: Spea2Model>(
+    spea2_model: &mut S,
+    model: &mut Model,
+    config: &DynamicConfig,
+    generations: usize,
+) -> Result<Vec<usize>, Spea2Error> {
+    let mut changes: Vec<usize> = vec![];
+    let mut hypermutation: Option<(MutationSchedule, usize)> = None;
+
+    for _ in 0..generations {
+        if detect_change(spea2_model, model, config.sentinel_count, config.tolerance) {
+            changes.push(model.generation);
+            respond(spea2_model, model, &config.response);
+            if let ChangeResponse::Hypermutation { rate, generations } = config.response {
+                let schedule = match hypermutation.take() {
+                    Some((schedule, _)) => schedule,
+                    None => model.mutation_schedule.clone(),
+                };
+                model.mutation_schedule = MutationSchedule::Constant(rate);
+                hypermutation = Some((schedule, generations));
+            }
+        }
+
+        let mut mutation = spea2_model.get_mutation_operator();
+        evolve(model, &mut mutation)?;
+
+        if let Some((schedule, remaining)) = hypermutation.take() {
+            if remaining > 1 {
+                hypermutation = Some((schedule, remaining - 1));
+            } else {
+                model.mutation_schedule = schedule;
+            }
+        }
+    }
+
+    if let Some((schedule, _)) = hypermutation {
+        model.mutation_schedule = schedule;
+    }
+    Ok(changes)
+}
+
+/// Re-evaluates up to `sentinel_count` archive members, or population members while the archive
+/// is still empty, and reports whether any of their values moved by more than `tolerance`.
+fn detect_change<S: Spea2Model>(
+    spea2_model: &mut S,
+    model: &mut Model,
+    sentinel_count: usize,
+    tolerance: f32,
+) -> bool {
+    let mut evaluations = 0;
+    let sentinels = match model.archive.is_empty() {
+        true => &model.population,
+        false => &model.archive,
+    };
+    let changed = sentinels.iter().take(sentinel_count).any(|sentinel| {
+        evaluations += 1;
+        let mut item = sentinel.clone();
+        spea2_model.evaluate(&model.objectives, &mut item);
+        item.values
+            .iter()
+            .zip(sentinel.values.iter())
+            .any(|(a, b)| (a - b).abs() > tolerance)
+    });
+    model.evaluations += evaluations;
+    changed
+}
+
+fn respond<S: Spea2Model>(spea2_model: &mut S, model: &mut Model, response: &ChangeResponse) {
+    let objectives = &model.objectives;
+    model
+        .archive
+        .iter_mut()
+        .chain(model.population.iter_mut())
+        .for_each(|item| spea2_model.evaluate(objectives, item));
+    model.evaluations += model.archive.len() + model.population.len();
+
+    if let ChangeResponse::RandomImmigrants(proportion) = *response {
+        let count = (model.population.len() as f64 * proportion).round() as usize;
+        for i in 0..count.min(model.population.len()) {
+            match spea2_model.get_random_item(&model.objectives) {
+                Some(item) => model.population[i] = item,
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{self, MOCK_POPULATION_COUNT};
+
+    fn get_config(response: ChangeResponse) -> DynamicConfig {
+        DynamicConfig {
+            sentinel_count: 3,
+            tolerance: 0.001,
+            response,
+        }
+    }
+
+    fn get_static_model(spea2_model: &mut mocks::MockSpea2Model) -> Model {
+        let mut model = spea2_model.get_model();
+        model.mutation_schedule = MutationSchedule::Constant(0.0);
+        model
+    }
+
+    fn change_environment(spea2_model: &mut mocks::MockSpea2Model) {
+        spea2_model.custom_data.iter_mut().for_each(|custom_data| {
+            custom_data.values[1] += 10.0;
+        });
+    }
+
+    #[test]
+    fn dynamic_detect_change() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = get_static_model(&mut spea2_model);
+        let config = get_config(ChangeResponse::ReEvaluate);
+
+        evolve_dynamic(&mut spea2_model, &mut model, &config, 2).unwrap();
+        assert!(!detect_change(&mut spea2_model, &mut model, 3, 0.001));
+
+        change_environment(&mut spea2_model);
+        assert!(detect_change(&mut spea2_model, &mut model, 3, 0.001));
+    }
+
+    #[test]
+    fn dynamic_detect_change_before_first_evolve() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = get_static_model(&mut spea2_model);
+        assert!(model.archive.is_empty());
+        assert!(!detect_change(&mut spea2_model, &mut model, 3, 0.001));
+
+        change_environment(&mut spea2_model);
+        assert!(detect_change(&mut spea2_model, &mut model, 3, 0.001));
+        assert_eq!(model.evaluations, 4);
+    }
+
+    #[test]
+    fn dynamic_evolve_dynamic_re_evaluate() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = get_static_model(&mut spea2_model);
+        let config = get_config(ChangeResponse::ReEvaluate);
+
+        let changes = evolve_dynamic(&mut spea2_model, &mut model, &config, 3).unwrap();
+        assert!(changes.is_empty());
+
+        change_environment(&mut spea2_model);
+        let changes = evolve_dynamic(&mut spea2_model, &mut model, &config, 3).unwrap();
+        assert_eq!(changes, [3]);
+        model.archive.iter().for_each(|item| {
+            let index = item.custom_data_index.unwrap();
+            assert_eq!(item.values, spea2_model.custom_data[index].values);
+        });
+    }
+
+    #[test]
+    fn dynamic_evolve_dynamic_random_immigrants() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = get_static_model(&mut spea2_model);
+        let config = get_config(ChangeResponse::RandomImmigrants(0.5));
+
+        evolve_dynamic(&mut spea2_model, &mut model, &config, 2).unwrap();
+        change_environment(&mut spea2_model);
+        evolve_dynamic(&mut spea2_model, &mut model, &config, 1).unwrap();
+
+        assert_eq!(spea2_model.custom_data.len(), MOCK_POPULATION_COUNT + 5);
+    }
+
+    #[test]
+    fn dynamic_evolve_dynamic_hypermutation() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = get_static_model(&mut spea2_model);
+        let config = get_config(ChangeResponse::Hypermutation {
+            rate: 1.0,
+            generations: 2,
+        });
+
+        evolve_dynamic(&mut spea2_model, &mut model, &config, 2).unwrap();
+        change_environment(&mut spea2_model);
+
+        evolve_dynamic(&mut spea2_model, &mut model, &config, 1).unwrap();
+        assert_eq!(model.effective_mutation_rate, 1.0);
+        assert!(matches!(
+            model.mutation_schedule,
+            MutationSchedule::Constant(rate) if rate == 0.0
+        ));
+    }
+}
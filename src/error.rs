@@ -0,0 +1,28 @@
+use std::{error::Error, fmt};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Spea2Error {
+    EmptyModel,
+    NonFiniteValue { objective: usize, value: f32 },
+    ValueCountMismatch { expected: usize, found: usize },
+}
+
+impl fmt::Display for Spea2Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Spea2Error::EmptyModel => write!(f, "the population and archive are both empty"),
+            Spea2Error::NonFiniteValue { objective, value } => {
+                write!(
+                    f,
+                    "objective {} has the non-finite value {}",
+                    objective, value
+                )
+            }
+            Spea2Error::ValueCountMismatch { expected, found } => {
+                write!(f, "expected {} objective values, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl Error for Spea2Error {}
@@ -0,0 +1,314 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::error::Spea2Error;
+use crate::random;
+
+/// A configuration run `runs` times, the i-th run seeded with `base_seed + i`.
+#[derive(Debug, Clone)]
+pub struct Experiment {
+    pub name: String,
+    pub runs: usize,
+    pub base_seed: u64,
+    /// Names of the final indicator values each run returns, in order.
+    pub indicators: Vec<String>,
+    /// Runs in flight at once; 0 uses the available parallelism.
+    pub threads: usize,
+}
+
+impl Experiment {
+    pub fn new(name: &str, runs: usize, indicators: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            runs,
+            base_seed: 0,
+            indicators: indicators.iter().map(|name| name.to_string()).collect(),
+            threads: 0,
+        }
+    }
+
+    /// Calls `run` once per seed, in parallel. The calling thread's generator is seeded before each
+    /// call, so `run` should build its model and operators inside the closure and draw any random
+    /// numbers from `random::get_rng`.
+    pub fn run<F>(&self, run: F) -> Result<ExperimentResult, Spea2Error>
+    where
+        F: Fn(u64) -> Result<Vec<f64>, Spea2Error> + Sync,
+    {
+        let seeds: Vec<u64> = (0..self.runs as u64).map(|i| self.base_seed + i).collect();
+        let threads = match self.threads {
+            0 => thread::available_parallelism().map_or(1, |threads| threads.get()),
+            threads => threads,
+        };
+        let next = AtomicUsize::new(0);
+        let values = Mutex::new(vec![vec![]; seeds.len()]);
+
+        thread::scope(|scope| {
+            (0..threads.min(seeds.len()))
+                .map(|_| {
+                    scope.spawn(|| loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&seed) = seeds.get(i) else {
+                            return Ok(());
+                        };
+                        random::seed(seed);
+                        let result = run(seed)?;
+                        values.lock().unwrap()[i] = result;
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .try_for_each(|handle| handle.join().unwrap())
+        })?;
+
+        Ok(ExperimentResult {
+            name: self.name.clone(),
+            indicators: self.indicators.clone(),
+            seeds,
+            values: values.into_inner().unwrap(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExperimentResult {
+    pub name: String,
+    pub indicators: Vec<String>,
+    pub seeds: Vec<u64>,
+    /// The indicator values returned by each run, in seed order.
+    pub values: Vec<Vec<f64>>,
+}
+
+impl ExperimentResult {
+    pub fn get_values(&self, indicator: usize) -> Vec<f64> {
+        self.values.iter().map(|values| values[indicator]).collect()
+    }
+
+    pub fn get_summary(&self, indicator: usize) -> Summary {
+        Summary::new(&self.get_values(indicator))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Summary {
+    pub min: f64,
+    pub lower_quartile: f64,
+    pub median: f64,
+    pub upper_quartile: f64,
+    pub max: f64,
+}
+
+impl Summary {
+    pub fn new(values: &[f64]) -> Self {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        Self {
+            min: get_quantile(&sorted, 0.0),
+            lower_quartile: get_quantile(&sorted, 0.25),
+            median: get_quantile(&sorted, 0.5),
+            upper_quartile: get_quantile(&sorted, 0.75),
+            max: get_quantile(&sorted, 1.0),
+        }
+    }
+
+    pub fn get_iqr(&self) -> f64 {
+        self.upper_quartile - self.lower_quartile
+    }
+}
+
+/// Linear interpolation between the closest ranks.
+fn get_quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let position = q * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankSumTest {
+    /// The Mann-Whitney U statistic of the first sample.
+    pub u: f64,
+    pub z: f64,
+    /// Two-sided.
+    pub p_value: f64,
+}
+
+/// The Mann-Whitney U / Wilcoxon rank-sum test, using the normal approximation with tie and
+/// continuity corrections, which is reasonable from around 8 runs per sample.
+pub fn rank_sum_test(a: &[f64], b: &[f64]) -> RankSumTest {
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let n = n1 + n2;
+    let mut pooled: Vec<(f64, bool)> = a
+        .iter()
+        .map(|&value| (value, true))
+        .chain(b.iter().map(|&value| (value, false)))
+        .collect();
+    pooled.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let mut rank_sum = 0.0;
+    let mut ties = 0.0;
+    let mut start = 0;
+    while start < pooled.len() {
+        let end = start
+            + pooled[start..]
+                .iter()
+                .take_while(|(value, _)| *value == pooled[start].0)
+                .count();
+        let rank = (start + end + 1) as f64 / 2.0;
+        let count = (end - start) as f64;
+        rank_sum += rank
+            * pooled[start..end]
+                .iter()
+                .filter(|(_, first)| *first)
+                .count() as f64;
+        ties += count.powi(3) - count;
+        start = end;
+    }
+
+    let u = rank_sum - n1 * (n1 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)));
+    if variance <= 0.0 {
+        return RankSumTest {
+            u,
+            z: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let difference = u - mean;
+    let z = (difference.abs() - 0.5).max(0.0).copysign(difference) / variance.sqrt();
+    RankSumTest {
+        u,
+        z,
+        p_value: erfc(z.abs() / std::f64::consts::SQRT_2).min(1.0),
+    }
+}
+
+/// Complementary error function, accurate to about 1.2e-7 (Numerical Recipes' `erfcc`).
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let y = t
+        * (-x * x - 1.26551223
+            + t * (1.00002368
+                + t * (0.37409196
+                    + t * (0.09678418
+                        + t * (-0.18628806
+                            + t * (0.27886807
+                                + t * (-1.13520398
+                                    + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+            .exp();
+    if x >= 0.0 {
+        y
+    } else {
+        2.0 - y
+    }
+}
+
+/// A plain-text table with the median and IQR of every indicator for each configuration, and the
+/// rank-sum p-value of each configuration against the first.
+pub fn get_report(results: &[ExperimentResult]) -> String {
+    let mut report = String::new();
+    let Some(baseline) = results.first() else {
+        return report;
+    };
+    for (indicator, indicator_name) in baseline.indicators.iter().enumerate() {
+        writeln!(report, "{}", indicator_name).unwrap();
+        writeln!(
+            report,
+            "  {:<24} {:>14} {:>14} {:>10}",
+            "configuration", "median", "iqr", "p"
+        )
+        .unwrap();
+        let baseline_values = baseline.get_values(indicator);
+        results.iter().for_each(|result| {
+            let summary = result.get_summary(indicator);
+            let p_value = rank_sum_test(&result.get_values(indicator), &baseline_values).p_value;
+            writeln!(
+                report,
+                "  {:<24} {:>14.6} {:>14.6} {:>10.4}",
+                result.name,
+                summary.median,
+                summary.get_iqr(),
+                p_value
+            )
+            .unwrap();
+        });
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evolve_with;
+    use crate::indicators::distance;
+    use crate::mocks::{self, MOCK_MAX_OBJECTIVE_VALUE, MOCK_MIN_OBJECTIVE_VALUE};
+
+    fn run_mock(generations: usize) -> Result<Vec<f64>, Spea2Error> {
+        let mut model = mocks::get_model_with_real_genomes();
+        let mut operators = mocks::get_real_operators();
+        for _ in 0..generations {
+            evolve_with(&mut model, &mut operators)?;
+        }
+        let front = vec![vec![MOCK_MAX_OBJECTIVE_VALUE, MOCK_MIN_OBJECTIVE_VALUE]];
+        let igd =
+            distance::inverted_generational_distance(&model.objectives, &model.archive, &front);
+        Ok(vec![igd, model.archive.len() as f64])
+    }
+
+    #[test]
+    fn experiment_run_is_reproducible() {
+        let mut experiment = Experiment::new("mock", 6, &["igd", "archive_size"]);
+        experiment.threads = 3;
+
+        let first = experiment.run(|_| run_mock(10)).unwrap();
+        let second = experiment.run(|_| run_mock(10)).unwrap();
+
+        assert_eq!(first.seeds, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(first.values, second.values);
+        assert_ne!(first.values[0], first.values[1]);
+    }
+
+    #[test]
+    fn experiment_compare_configurations() {
+        let short = Experiment::new("short", 8, &["igd", "archive_size"])
+            .run(|_| run_mock(1))
+            .unwrap();
+        let long = Experiment::new("long", 8, &["igd", "archive_size"])
+            .run(|_| run_mock(60))
+            .unwrap();
+
+        assert!(long.get_summary(0).median < short.get_summary(0).median);
+        assert!(rank_sum_test(&long.get_values(0), &short.get_values(0)).p_value < 0.01);
+        let report = get_report(&[short, long]);
+        assert!(report.contains("igd") && report.contains("long"));
+    }
+
+    #[test]
+    fn experiment_summary() {
+        let summary = Summary::new(&[4.0, 1.0, 3.0, 2.0, 5.0]);
+        assert_eq!(summary.median, 3.0);
+        assert_eq!(summary.lower_quartile, 2.0);
+        assert_eq!(summary.upper_quartile, 4.0);
+        assert_eq!(summary.get_iqr(), 2.0);
+        assert_eq!(Summary::new(&[1.0, 2.0]).median, 1.5);
+    }
+
+    #[test]
+    fn experiment_rank_sum_test() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [6.0, 7.0, 8.0, 9.0, 10.0];
+        let test = rank_sum_test(&a, &b);
+        assert_eq!(test.u, 0.0);
+        assert!((test.p_value - 0.0122).abs() < 1e-3, "{}", test.p_value);
+        assert_eq!(rank_sum_test(&b, &a).u, 25.0);
+
+        let tied = rank_sum_test(&[1.0, 2.0, 2.0], &[2.0, 3.0]);
+        assert_eq!(tied.u, 1.0);
+        assert_eq!(rank_sum_test(&a, &a).p_value, 1.0);
+        assert_eq!(rank_sum_test(&[1.0; 4], &[1.0; 4]).p_value, 1.0);
+    }
+}
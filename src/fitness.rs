@@ -1,4 +1,4 @@
-use crate::model::{Direction, Model, ModelItem};
+use crate::model::{Direction, Model, ModelItem, Objective};
 
 pub fn set_fitness(model: &mut Model) -> (Vec<Vec<f32>>, Vec<Vec<usize>>, Vec<f32>) {
     let mut union: Vec<&mut ModelItem> = model
@@ -8,7 +8,6 @@ pub fn set_fitness(model: &mut Model) -> (Vec<Vec<f32>>, Vec<Vec<usize>>, Vec<f3
         .collect();
 
     let len_union = union.len();
-    let len_objectives = model.objectives.len();
     let kth = (len_union as f64).sqrt() as usize;
     let mut distances: Vec<Vec<f32>> = vec![vec![0.0; len_union]; len_union];
     let mut strengths: Vec<f32> = vec![0.0; len_union];
@@ -16,45 +15,20 @@ pub fn set_fitness(model: &mut Model) -> (Vec<Vec<f32>>, Vec<Vec<usize>>, Vec<f3
 
     for i in 0..len_union {
         for j in i + 1..len_union {
-            let mut distance: f32 = 0.0;
-            let mut i_dom_j = false;
-            let mut j_dom_i = false;
-            let mut dominated = false;
-
-            for k in 0..len_objectives {
-                distance += (union[i].values[k] - union[j].values[k]).powi(2);
-                if !dominated {
-                    let (dv1, dv2) = match model.objectives[k].direction {
-                        Direction::Maximised => (union[j].values[k], union[i].values[k]),
-                        Direction::Minimised => (union[i].values[k], union[j].values[k]),
-                    };
-
-                    if dv1 < dv2 {
-                        i_dom_j = true;
-                        if j_dom_i {
-                            dominated = true;
-                        }
-                    } else if dv2 < dv1 {
-                        j_dom_i = true;
-                        if i_dom_j {
-                            dominated = true;
-                        }
-                    } else {
-                        i_dom_j = false;
-                        j_dom_i = false;
-                        dominated = true;
-                    }
-                }
-            }
-
-            distance = distance.sqrt();
+            let distance = get_distance(union[i], union[j]);
             distances[i][j] = distance;
             distances[j][i] = distance;
 
-            if i_dom_j && !j_dom_i {
+            let (i_dom_j, j_dom_i) = get_dominance(
+                &model.objectives,
+                model.dominance_confidence,
+                union[i],
+                union[j],
+            );
+            if i_dom_j {
                 strengths[i] += 1.0;
                 dominators[j].push(i);
-            } else if j_dom_i && !i_dom_j {
+            } else if j_dom_i {
                 strengths[j] += 1.0;
                 dominators[i].push(j);
             }
@@ -68,13 +42,57 @@ pub fn set_fitness(model: &mut Model) -> (Vec<Vec<f32>>, Vec<Vec<usize>>, Vec<f3
         }
         let mut sorted = distances[i].clone();
         quickersort::sort_floats(&mut sorted);
-        let density_estimate = 1.0 / sorted[kth];
+        let density_estimate = 1.0 / (sorted[kth] + 2.0);
         union[i].fitness = raw_fitness + density_estimate;
     }
 
     (distances, dominators, strengths)
 }
 
+pub(crate) fn get_distance(a: &ModelItem, b: &ModelItem) -> f32 {
+    let mut distance: f32 = 0.0;
+    for k in 0..a.values.len() {
+        distance += (a.values[k] - b.values[k]).powi(2);
+    }
+    distance.sqrt()
+}
+
+/// Whether `a` dominates `b` and whether `b` dominates `a`, each requiring the dominating item to
+/// be better in every objective by more than `dominance_confidence` standard errors.
+pub(crate) fn get_dominance(
+    objectives: &[Objective],
+    dominance_confidence: f32,
+    a: &ModelItem,
+    b: &ModelItem,
+) -> (bool, bool) {
+    let mut a_dom_b = false;
+    let mut b_dom_a = false;
+
+    for (k, objective) in objectives.iter().enumerate() {
+        let (dv1, dv2) = match objective.direction {
+            Direction::Maximised => (b.values[k], a.values[k]),
+            Direction::Minimised => (a.values[k], b.values[k]),
+        };
+
+        let margin = dominance_confidence * a.get_standard_error(k).hypot(b.get_standard_error(k));
+
+        if dv2 - dv1 > margin {
+            a_dom_b = true;
+            if b_dom_a {
+                return (false, false);
+            }
+        } else if dv1 - dv2 > margin {
+            b_dom_a = true;
+            if a_dom_b {
+                return (false, false);
+            }
+        } else {
+            return (false, false);
+        }
+    }
+    (a_dom_b, b_dom_a)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,6 +130,37 @@ mod tests {
         assert_eq!(strengths[2], 0.0);
     }
 
+    #[test]
+    fn fitness_strengths_with_confidence() {
+        let mut model = mocks::get_model_basic();
+        model.population[1].samples = 4;
+        model.population[1].variances = vec![16.0, 16.0];
+        model.archive[0].samples = 4;
+        model.archive[0].variances = vec![16.0, 16.0];
+
+        let (_, _, strengths) = set_fitness(&mut model);
+        assert_eq!(strengths[1], 1.0);
+
+        model.dominance_confidence = 2.0;
+        let (_, dominators, strengths) = set_fitness(&mut model);
+        assert_eq!(strengths[1], 0.0);
+        assert!(dominators[2].is_empty());
+    }
+
+    #[test]
+    fn fitness_crowded_non_dominated() {
+        let mut model = mocks::get_model_basic();
+        model.archive.clear();
+        model.population = (0..5)
+            .map(|i| {
+                let v = i as f32 * 0.1;
+                ModelItem::new(vec![v, v], None)
+            })
+            .collect();
+        set_fitness(&mut model);
+        assert!(model.population.iter().all(|item| item.fitness < 1.0));
+    }
+
     #[test]
     fn fitness_fitness() {
         let mut model = mocks::get_model_basic();
@@ -0,0 +1,168 @@
+use super::{get_distance, get_normalised};
+use crate::model::{ModelItem, Objective};
+
+/// Only the amount by which `a` is worse than `b`, so that `a` is not penalised for improving on `b`.
+fn get_dominance_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).max(0.0).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+fn get_min_distances(
+    from: &[Vec<f64>],
+    to: &[Vec<f64>],
+    distance: fn(&[f64], &[f64]) -> f64,
+) -> Vec<f64> {
+    from.iter()
+        .map(|a| {
+            to.iter()
+                .map(|b| distance(a, b))
+                .fold(f64::INFINITY, f64::min)
+        })
+        .collect()
+}
+
+fn get_power_mean(distances: &[f64], p: f64) -> f64 {
+    if distances.is_empty() {
+        return f64::INFINITY;
+    }
+    (distances.iter().map(|d| d.powf(p)).sum::<f64>() / distances.len() as f64).powf(1.0 / p)
+}
+
+fn get_sets(
+    objectives: &[Objective],
+    items: &[ModelItem],
+    front: &[Vec<f32>],
+) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+    let items = items
+        .iter()
+        .map(|item| get_normalised(objectives, &item.values))
+        .collect();
+    let front = front
+        .iter()
+        .map(|values| get_normalised(objectives, values))
+        .collect();
+    (items, front)
+}
+
+/// GD: the mean distance from each item to the nearest point of the reference `front`, in
+/// normalised objective space. Measures convergence only.
+pub fn generational_distance(
+    objectives: &[Objective],
+    items: &[ModelItem],
+    front: &[Vec<f32>],
+) -> f64 {
+    let (items, front) = get_sets(objectives, items, front);
+    get_power_mean(&get_min_distances(&items, &front, get_distance), 1.0)
+}
+
+/// IGD: the mean distance from each point of the reference `front` to the nearest item, which
+/// rewards both convergence and coverage of the front.
+pub fn inverted_generational_distance(
+    objectives: &[Objective],
+    items: &[ModelItem],
+    front: &[Vec<f32>],
+) -> f64 {
+    let (items, front) = get_sets(objectives, items, front);
+    get_power_mean(&get_min_distances(&front, &items, get_distance), 1.0)
+}
+
+/// IGD+: IGD counting only the objectives in which the item is worse than the reference point,
+/// making it weakly Pareto compliant.
+pub fn inverted_generational_distance_plus(
+    objectives: &[Objective],
+    items: &[ModelItem],
+    front: &[Vec<f32>],
+) -> f64 {
+    let (items, front) = get_sets(objectives, items, front);
+    let distances = get_min_distances(&front, &items, |r, a| get_dominance_distance(a, r));
+    get_power_mean(&distances, 1.0)
+}
+
+/// Schütze et al.'s averaged Hausdorff distance Δp, the larger of the power means of order `p`
+/// of the GD and IGD distances.
+pub fn averaged_hausdorff_distance(
+    objectives: &[Objective],
+    items: &[ModelItem],
+    front: &[Vec<f32>],
+    p: f64,
+) -> f64 {
+    let (items, front) = get_sets(objectives, items, front);
+    let gd = get_power_mean(&get_min_distances(&items, &front, get_distance), p);
+    let igd = get_power_mean(&get_min_distances(&front, &items, get_distance), p);
+    gd.max(igd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn get_items(values: &[[f32; 2]]) -> Vec<ModelItem> {
+        values
+            .iter()
+            .map(|values| ModelItem::new(values.to_vec(), None))
+            .collect()
+    }
+
+    fn get_front() -> Vec<Vec<f32>> {
+        vec![vec![100.0, 50.0], vec![50.0, 0.0]]
+    }
+
+    #[test]
+    fn distance_get_normalised() {
+        let objectives = mocks::get_model_basic().objectives;
+        assert_eq!(get_normalised(&objectives, &[100.0, 0.0]), [0.0, 0.0]);
+        assert_eq!(get_normalised(&objectives, &[25.0, 75.0]), [0.75, 0.75]);
+    }
+
+    #[test]
+    fn distance_generational_distance() {
+        let objectives = mocks::get_model_basic().objectives;
+        let items = get_items(&[[100.0, 50.0], [50.0, 30.0]]);
+
+        let gd = generational_distance(&objectives, &items, &get_front());
+        let igd = inverted_generational_distance(&objectives, &items, &get_front());
+
+        assert!((gd - 0.15).abs() < EPSILON, "{}", gd);
+        assert!((igd - 0.15).abs() < EPSILON, "{}", igd);
+        assert_eq!(
+            inverted_generational_distance(&objectives, &get_items(&[]), &get_front()),
+            f64::INFINITY
+        );
+    }
+
+    #[test]
+    fn distance_inverted_generational_distance_plus() {
+        let objectives = mocks::get_model_basic().objectives;
+        let better = get_items(&[[100.0, 40.0], [60.0, 0.0]]);
+        let worse = get_items(&[[90.0, 50.0], [50.0, 10.0]]);
+
+        assert!(inverted_generational_distance(&objectives, &better, &get_front()) > 0.0);
+        assert_eq!(
+            inverted_generational_distance_plus(&objectives, &better, &get_front()),
+            0.0
+        );
+        let igd_plus = inverted_generational_distance_plus(&objectives, &worse, &get_front());
+        assert!((igd_plus - 0.1).abs() < EPSILON, "{}", igd_plus);
+    }
+
+    #[test]
+    fn distance_averaged_hausdorff_distance() {
+        let objectives = mocks::get_model_basic().objectives;
+        let items = get_items(&[[100.0, 50.0], [50.0, 0.0], [0.0, 100.0]]);
+
+        assert_eq!(
+            inverted_generational_distance(&objectives, &items, &get_front()),
+            0.0
+        );
+        let hausdorff = averaged_hausdorff_distance(&objectives, &items, &get_front(), 1.0);
+        let gd = generational_distance(&objectives, &items, &get_front());
+        assert!((hausdorff - gd).abs() < EPSILON);
+        assert!(hausdorff > 0.0);
+    }
+}
@@ -0,0 +1,271 @@
+use super::{get_distance, get_minimised, get_normalised, weakly_dominates};
+use crate::model::{Model, ModelItem, Objective};
+
+/// Schott's spacing: the standard deviation of each item's Manhattan distance to its nearest
+/// neighbour in normalised objective space. 0 means evenly spaced.
+pub fn spacing(objectives: &[Objective], items: &[ModelItem]) -> f64 {
+    if items.len() < 2 {
+        return 0.0;
+    }
+    let points = get_normalised_points(objectives, items);
+    let distances: Vec<f64> = points
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, b)| a.iter().zip(b).map(|(a, b)| (a - b).abs()).sum::<f64>())
+                .fold(f64::INFINITY, f64::min)
+        })
+        .collect();
+    let mean = distances.iter().sum::<f64>() / distances.len() as f64;
+    let variance =
+        distances.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / (distances.len() - 1) as f64;
+    variance.sqrt()
+}
+
+/// Deb's spread Δ for two objectives: how unevenly consecutive items are spaced along the front,
+/// plus how far the outermost items fall short of the extremes of the reference `front`. Pass an
+/// empty `front` to measure the spacing alone. 0 means evenly spaced and reaching the extremes.
+pub fn spread(objectives: &[Objective], items: &[ModelItem], front: &[Vec<f32>]) -> f64 {
+    let mut points = get_normalised_points(objectives, items);
+    if points.len() < 2 {
+        return 1.0;
+    }
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]).then(b[1].total_cmp(&a[1])));
+    let gaps: Vec<f64> = points
+        .windows(2)
+        .map(|pair| get_distance(&pair[0], &pair[1]))
+        .collect();
+    let mean = gaps.iter().sum::<f64>() / gaps.len() as f64;
+
+    let front: Vec<Vec<f64>> = front
+        .iter()
+        .map(|values| get_normalised(objectives, values))
+        .collect();
+    let get_extreme_distance = |k: usize, point: &[f64]| {
+        front
+            .iter()
+            .min_by(|a, b| a[k].total_cmp(&b[k]))
+            .map(|extreme| get_distance(extreme, point))
+            .unwrap_or(0.0)
+    };
+    let first = get_extreme_distance(0, &points[0]);
+    let last = get_extreme_distance(1, &points[points.len() - 1]);
+
+    let deviation: f64 = gaps.iter().map(|gap| (gap - mean).abs()).sum();
+    let denominator = first + last + gaps.len() as f64 * mean;
+    match denominator > 0.0 {
+        true => (first + last + deviation) / denominator,
+        false => 0.0,
+    }
+}
+
+/// Zitzler's C(A, B): the fraction of `b` weakly dominated by at least one item of `a`. Not
+/// symmetric, so compare both C(A, B) and C(B, A).
+pub fn coverage(objectives: &[Objective], a: &[ModelItem], b: &[ModelItem]) -> f64 {
+    if b.is_empty() {
+        return 0.0;
+    }
+    let a: Vec<Vec<f64>> = a
+        .iter()
+        .map(|item| get_minimised(objectives, &item.values))
+        .collect();
+    let covered = b
+        .iter()
+        .filter(|item| {
+            let point = get_minimised(objectives, &item.values);
+            a.iter().any(|other| weakly_dominates(other, &point))
+        })
+        .count();
+    covered as f64 / b.len() as f64
+}
+
+/// The R2 indicator: the mean over `weights` of the best weighted Tchebycheff distance of any item
+/// from the ideal point, the best bound of every objective. Lower is better.
+pub fn r2(objectives: &[Objective], items: &[ModelItem], weights: &[Vec<f64>]) -> f64 {
+    let points = get_normalised_points(objectives, items);
+    if weights.is_empty() {
+        return 0.0;
+    }
+    weights
+        .iter()
+        .map(|weight| {
+            points
+                .iter()
+                .map(|point| {
+                    weight
+                        .iter()
+                        .zip(point)
+                        .map(|(w, x)| w * x.abs())
+                        .fold(0.0, f64::max)
+                })
+                .fold(f64::INFINITY, f64::min)
+        })
+        .sum::<f64>()
+        / weights.len() as f64
+}
+
+/// Das and Dennis's evenly spread weight vectors: every vector of `dimensions` multiples of
+/// `1 / divisions` that sum to 1.
+pub fn get_weight_vectors(dimensions: usize, divisions: usize) -> Vec<Vec<f64>> {
+    fn fill(prefix: &mut Vec<usize>, left: usize, dimensions: usize, out: &mut Vec<Vec<usize>>) {
+        if prefix.len() == dimensions - 1 {
+            prefix.push(left);
+            out.push(prefix.clone());
+            prefix.pop();
+            return;
+        }
+        for i in 0..=left {
+            prefix.push(i);
+            fill(prefix, left - i, dimensions, out);
+            prefix.pop();
+        }
+    }
+    if dimensions == 0 {
+        return vec![];
+    }
+    let mut out = vec![];
+    fill(&mut vec![], divisions, dimensions, &mut out);
+    out.into_iter()
+        .map(|counts| {
+            counts
+                .into_iter()
+                .map(|count| count as f64 / divisions.max(1) as f64)
+                .collect()
+        })
+        .collect()
+}
+
+fn get_normalised_points(objectives: &[Objective], items: &[ModelItem]) -> Vec<Vec<f64>> {
+    items
+        .iter()
+        .map(|item| get_normalised(objectives, &item.values))
+        .collect()
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DiversityStats {
+    pub generation: usize,
+    pub spacing: f64,
+    pub spread: f64,
+    pub r2: f64,
+    /// C(current archive, previous archive).
+    pub coverage_of_previous: f64,
+    /// C(previous archive, current archive).
+    pub coverage_by_previous: f64,
+}
+
+/// Records `DiversityStats` for `Model::archive`; call `record` after each generation.
+#[derive(Debug, Default, Clone)]
+pub struct DiversityMonitor {
+    pub weights: Vec<Vec<f64>>,
+    pub history: Vec<DiversityStats>,
+    previous: Vec<ModelItem>,
+}
+
+impl DiversityMonitor {
+    pub fn new(weights: Vec<Vec<f64>>) -> Self {
+        Self {
+            weights,
+            ..Default::default()
+        }
+    }
+
+    pub fn record(&mut self, model: &Model) -> &DiversityStats {
+        let objectives = &model.objectives;
+        let archive = &model.archive;
+        self.history.push(DiversityStats {
+            generation: model.generation,
+            spacing: spacing(objectives, archive),
+            spread: spread(objectives, archive, &[]),
+            r2: r2(objectives, archive, &self.weights),
+            coverage_of_previous: coverage(objectives, archive, &self.previous),
+            coverage_by_previous: coverage(objectives, &self.previous, archive),
+        });
+        self.previous = archive.clone();
+        self.history.last().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn get_items(values: &[[f32; 2]]) -> Vec<ModelItem> {
+        values
+            .iter()
+            .map(|values| ModelItem::new(values.to_vec(), None))
+            .collect()
+    }
+
+    fn get_even_items() -> Vec<ModelItem> {
+        get_items(&[[100.0, 50.0], [75.0, 25.0], [50.0, 0.0]])
+    }
+
+    #[test]
+    fn diversity_spacing() {
+        let objectives = mocks::get_model_basic().objectives;
+        assert_eq!(spacing(&objectives, &get_even_items()), 0.0);
+
+        let uneven = get_items(&[[100.0, 50.0], [90.0, 40.0], [50.0, 0.0]]);
+        assert!(spacing(&objectives, &uneven) > 0.0);
+    }
+
+    #[test]
+    fn diversity_spread() {
+        let objectives = mocks::get_model_basic().objectives;
+        let front = vec![vec![100.0, 50.0], vec![50.0, 0.0]];
+        assert!(spread(&objectives, &get_even_items(), &front).abs() < EPSILON);
+
+        let short = get_items(&[[90.0, 40.0], [75.0, 25.0], [60.0, 10.0]]);
+        let actual = spread(&objectives, &short, &front);
+        assert!((actual - 0.4).abs() < EPSILON, "{}", actual);
+    }
+
+    #[test]
+    fn diversity_coverage() {
+        let objectives = mocks::get_model_basic().objectives;
+        let a = get_even_items();
+        let b = get_items(&[[90.0, 50.0], [75.0, 25.0], [10.0, 0.0], [100.0, 0.0]]);
+
+        assert_eq!(coverage(&objectives, &a, &b), 0.75);
+        assert_eq!(coverage(&objectives, &b, &a), 1.0);
+        assert_eq!(coverage(&objectives, &a, &[]), 0.0);
+    }
+
+    #[test]
+    fn diversity_r2() {
+        let objectives = mocks::get_model_basic().objectives;
+        let weights = get_weight_vectors(2, 2);
+        assert_eq!(weights, [[0.0, 1.0], [0.5, 0.5], [1.0, 0.0]]);
+
+        let ideal = get_items(&[[100.0, 0.0]]);
+        assert_eq!(r2(&objectives, &ideal, &weights), 0.0);
+
+        let items = get_even_items();
+        let expected = (0.0 + 0.125 + 0.0) / 3.0;
+        assert!((r2(&objectives, &items, &weights) - expected).abs() < EPSILON);
+    }
+
+    #[test]
+    fn diversity_monitor_record() {
+        let mut model = mocks::get_model_with_archive();
+        let mut monitor = DiversityMonitor::new(get_weight_vectors(2, 4));
+
+        monitor.record(&model);
+        model.generation += 1;
+        let stats = monitor.record(&model).clone();
+
+        assert_eq!(monitor.history.len(), 2);
+        assert_eq!(monitor.history[0].coverage_of_previous, 0.0);
+        assert_eq!(stats.generation, 1);
+        assert_eq!(stats.coverage_of_previous, 1.0);
+        assert_eq!(stats.coverage_by_previous, 1.0);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::model::{Direction, ModelItem, Objective};
+
+/// The additive ε indicator I(A, B): the smallest amount every item of `b` can be made worse by in
+/// every objective, in the objectives' own units, so that each is weakly dominated by some item of
+/// `a`. At most 0 when `a` weakly dominates `b`, and negative when it strictly dominates all of
+/// `b`. Use a reference front as `b` for the unary indicator.
+pub fn additive_epsilon(objectives: &[Objective], a: &[ModelItem], b: &[ModelItem]) -> f64 {
+    get_epsilon(objectives, a, b, |a, b, direction| match direction {
+        Direction::Maximised => b - a,
+        Direction::Minimised => a - b,
+    })
+}
+
+/// The multiplicative ε indicator I(A, B): the smallest factor by which every item of `b` can be
+/// made worse in every objective so that each is weakly dominated by some item of `a`. At most 1
+/// when `a` weakly dominates `b`, and below 1 when it strictly dominates all of `b`. Only
+/// meaningful for strictly positive objective values.
+pub fn multiplicative_epsilon(objectives: &[Objective], a: &[ModelItem], b: &[ModelItem]) -> f64 {
+    get_epsilon(objectives, a, b, |a, b, direction| match direction {
+        Direction::Maximised => b / a,
+        Direction::Minimised => a / b,
+    })
+}
+
+fn get_epsilon(
+    objectives: &[Objective],
+    a: &[ModelItem],
+    b: &[ModelItem],
+    epsilon: fn(f64, f64, &Direction) -> f64,
+) -> f64 {
+    b.iter()
+        .map(|b| {
+            a.iter()
+                .map(|a| {
+                    objectives
+                        .iter()
+                        .map(|objective| {
+                            let k = objective.index;
+                            epsilon(a.values[k] as f64, b.values[k] as f64, &objective.direction)
+                        })
+                        .fold(f64::NEG_INFINITY, f64::max)
+                })
+                .fold(f64::INFINITY, f64::min)
+        })
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks;
+
+    fn get_items(values: &[[f32; 2]]) -> Vec<ModelItem> {
+        values
+            .iter()
+            .map(|values| ModelItem::new(values.to_vec(), None))
+            .collect()
+    }
+
+    #[test]
+    fn epsilon_additive() {
+        let objectives = mocks::get_model_basic().objectives;
+        let a = get_items(&[[10.0, 4.0], [8.0, 2.0]]);
+        let b = get_items(&[[9.0, 4.0], [6.0, 1.0]]);
+
+        assert_eq!(additive_epsilon(&objectives, &a, &b), 1.0);
+        assert_eq!(additive_epsilon(&objectives, &b, &a), 2.0);
+        assert_eq!(additive_epsilon(&objectives, &a, &a), 0.0);
+
+        let dominated = get_items(&[[7.0, 6.0]]);
+        assert_eq!(additive_epsilon(&objectives, &a, &dominated), -2.0);
+    }
+
+    #[test]
+    fn epsilon_multiplicative() {
+        let objectives = mocks::get_model_basic().objectives;
+        let a = get_items(&[[10.0, 4.0], [8.0, 2.0]]);
+        let b = get_items(&[[20.0, 4.0], [4.0, 1.0]]);
+
+        assert_eq!(multiplicative_epsilon(&objectives, &a, &b), 2.0);
+        assert_eq!(multiplicative_epsilon(&objectives, &b, &a), 2.0);
+        assert_eq!(multiplicative_epsilon(&objectives, &a, &a), 1.0);
+    }
+}
@@ -0,0 +1,240 @@
+use rand::Rng;
+
+use super::{get_minimised, get_non_dominated, get_points, weakly_dominates};
+use crate::model::{ModelItem, Objective};
+use crate::random;
+
+/// The volume of objective space dominated by `items` and bounded by `reference`, a point in the
+/// objectives' own units that should be worse than every item in every objective. Items that do
+/// not dominate the reference point contribute nothing.
+///
+/// Exact for any number of objectives, using dedicated sweeps for 2 and 3 and the WFG algorithm
+/// beyond that. WFG is exponential in the number of objectives, so consider
+/// `estimate_hypervolume` for many objectives.
+pub fn hypervolume(objectives: &[Objective], items: &[ModelItem], reference: &[f32]) -> f64 {
+    let reference = get_minimised(objectives, reference);
+    let points = get_bounded_points(objectives, items, &reference);
+    match reference.len() {
+        0 => 0.0,
+        1 => points
+            .iter()
+            .map(|point| reference[0] - point[0])
+            .fold(0.0, f64::max),
+        2 => get_hypervolume_2d(points, &reference),
+        3 => get_hypervolume_3d(points, &reference),
+        _ => get_hypervolume_wfg(get_non_dominated(points), &reference),
+    }
+}
+
+/// A Monte-Carlo estimate of `hypervolume` from `samples` uniform points in the box between the
+/// best value of each objective and the reference point.
+pub fn estimate_hypervolume(
+    objectives: &[Objective],
+    items: &[ModelItem],
+    reference: &[f32],
+    samples: usize,
+) -> f64 {
+    let mut rng = random::get_rng();
+    let reference = get_minimised(objectives, reference);
+    let points = get_non_dominated(get_bounded_points(objectives, items, &reference));
+    if points.is_empty() || samples == 0 {
+        return 0.0;
+    }
+
+    let ideal: Vec<f64> = (0..reference.len())
+        .map(|k| points.iter().map(|point| point[k]).fold(f64::MAX, f64::min))
+        .collect();
+    let volume = get_inclusive_hypervolume(&ideal, &reference);
+    let mut sample = vec![0.0; reference.len()];
+    let dominated = (0..samples)
+        .filter(|_| {
+            sample
+                .iter_mut()
+                .zip(ideal.iter().zip(reference.iter()))
+                .for_each(|(x, (min, max))| *x = rng.gen_range(*min..*max));
+            points.iter().any(|point| weakly_dominates(point, &sample))
+        })
+        .count();
+    volume * dominated as f64 / samples as f64
+}
+
+fn get_bounded_points(
+    objectives: &[Objective],
+    items: &[ModelItem],
+    reference: &[f64],
+) -> Vec<Vec<f64>> {
+    get_points(objectives, items)
+        .into_iter()
+        .filter(|point| point.iter().zip(reference).all(|(x, r)| x < r))
+        .collect()
+}
+
+fn get_inclusive_hypervolume(point: &[f64], reference: &[f64]) -> f64 {
+    point.iter().zip(reference).map(|(x, r)| r - x).product()
+}
+
+fn get_hypervolume_2d(mut points: Vec<Vec<f64>>, reference: &[f64]) -> f64 {
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    let mut volume = 0.0;
+    let mut previous = reference[1];
+    for point in points {
+        if point[1] < previous {
+            volume += (reference[0] - point[0]) * (previous - point[1]);
+            previous = point[1];
+        }
+    }
+    volume
+}
+
+/// Sweeps along the third objective, adding the 2D hypervolume of the points seen so far for each
+/// slab between consecutive values.
+fn get_hypervolume_3d(mut points: Vec<Vec<f64>>, reference: &[f64]) -> f64 {
+    points.sort_by(|a, b| a[2].total_cmp(&b[2]));
+    let mut volume = 0.0;
+    for i in 0..points.len() {
+        let depth = match points.get(i + 1) {
+            Some(next) => next[2] - points[i][2],
+            None => reference[2] - points[i][2],
+        };
+        if depth > 0.0 {
+            volume += depth * get_hypervolume_2d(points[..=i].to_vec(), reference);
+        }
+    }
+    volume
+}
+
+/// While, Bradstreet and Barone's WFG: the sum of each point's exclusive hypervolume relative to
+/// the points after it, each computed from the front those points leave behind when limited by it.
+fn get_hypervolume_wfg(mut points: Vec<Vec<f64>>, reference: &[f64]) -> f64 {
+    if reference.len() == 2 {
+        return get_hypervolume_2d(points, reference);
+    }
+    points.sort_by(|a, b| b[0].total_cmp(&a[0]));
+    (0..points.len())
+        .map(|i| {
+            let limited = points[i + 1..]
+                .iter()
+                .map(|other| {
+                    other
+                        .iter()
+                        .zip(points[i].iter())
+                        .map(|(a, b)| a.max(*b))
+                        .collect()
+                })
+                .collect();
+            get_inclusive_hypervolume(&points[i], reference)
+                - get_hypervolume_wfg(get_non_dominated(limited), reference)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BoundsPolicy, Direction};
+
+    fn get_objectives(count: usize) -> Vec<Objective> {
+        (0..count)
+            .map(|index| Objective {
+                name: format!("f{}", index),
+                direction: Direction::Minimised,
+                min: 0.0,
+                max: 1.0,
+                index,
+                bounds_policy: BoundsPolicy::Ignore,
+            })
+            .collect()
+    }
+
+    fn get_items(values: &[&[f32]]) -> Vec<ModelItem> {
+        values
+            .iter()
+            .map(|values| ModelItem::new(values.to_vec(), None))
+            .collect()
+    }
+
+    /// Counts the cells of a unit grid dominated by integer-valued points.
+    fn get_grid_hypervolume(values: &[&[f32]], reference: &[f32]) -> f64 {
+        let cells: usize = reference.iter().map(|&r| r as usize).product();
+        (0..cells)
+            .filter(|&cell| {
+                let mut rest = cell;
+                let corner: Vec<f32> = reference
+                    .iter()
+                    .map(|&r| {
+                        let x = rest % r as usize;
+                        rest /= r as usize;
+                        x as f32 + 1.0
+                    })
+                    .collect();
+                values
+                    .iter()
+                    .any(|values| values.iter().zip(corner.iter()).all(|(v, c)| v < c))
+            })
+            .count() as f64
+    }
+
+    #[test]
+    fn hypervolume_2d() {
+        let objectives = get_objectives(2);
+        let items = get_items(&[&[1.0, 3.0], &[2.0, 2.0], &[3.0, 1.0], &[3.0, 3.0]]);
+        assert_eq!(hypervolume(&objectives, &items, &[4.0, 4.0]), 6.0);
+    }
+
+    #[test]
+    fn hypervolume_direction_and_reference() {
+        let mut objectives = get_objectives(2);
+        objectives[0].direction = Direction::Maximised;
+        let items = get_items(&[&[3.0, 3.0], &[2.0, 2.0], &[1.0, 1.0], &[-1.0, 0.0]]);
+        assert_eq!(hypervolume(&objectives, &items, &[0.0, 4.0]), 6.0);
+    }
+
+    #[test]
+    fn hypervolume_exact_matches_grid() {
+        let values: &[&[f32]] = &[
+            &[1.0, 4.0, 2.0, 3.0],
+            &[2.0, 2.0, 4.0, 1.0],
+            &[4.0, 1.0, 1.0, 4.0],
+            &[3.0, 3.0, 3.0, 2.0],
+            &[1.0, 5.0, 5.0, 5.0],
+        ];
+        let reference = [6.0; 4];
+        for count in 2..=4 {
+            let objectives = get_objectives(count);
+            let truncated: Vec<&[f32]> = values.iter().map(|values| &values[..count]).collect();
+            assert_eq!(
+                hypervolume(&objectives, &get_items(&truncated), &reference[..count]),
+                get_grid_hypervolume(&truncated, &reference[..count]),
+                "{} objectives",
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn hypervolume_wfg_matches_3d() {
+        let mut rng = random::get_rng();
+        let points: Vec<Vec<f64>> = (0..30)
+            .map(|_| (0..3).map(|_| rng.gen_range(0.0..1.0)).collect())
+            .collect();
+        let reference = [1.0; 3];
+        let exact = get_hypervolume_3d(points.clone(), &reference);
+        let wfg = get_hypervolume_wfg(get_non_dominated(points), &reference);
+        assert!((exact - wfg).abs() < 1e-9, "{} - {}", exact, wfg);
+    }
+
+    #[test]
+    fn hypervolume_estimate() {
+        let objectives = get_objectives(3);
+        let items = get_items(&[&[1.0, 3.0, 2.0], &[2.0, 2.0, 3.0], &[3.0, 1.0, 1.0]]);
+        let reference = [4.0; 3];
+        let exact = hypervolume(&objectives, &items, &reference);
+        let estimate = estimate_hypervolume(&objectives, &items, &reference, 20000);
+        assert!(
+            (exact - estimate).abs() / exact < 0.05,
+            "{} - {}",
+            exact,
+            estimate
+        );
+    }
+}
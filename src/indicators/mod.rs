@@ -0,0 +1,71 @@
+use crate::model::{Direction, ModelItem, Objective};
+
+pub mod distance;
+pub mod diversity;
+pub mod epsilon;
+pub mod hypervolume;
+
+/// Item values as minimisation vectors, with maximised objectives negated.
+fn get_minimised(objectives: &[Objective], values: &[f32]) -> Vec<f64> {
+    objectives
+        .iter()
+        .map(|objective| {
+            let value = values[objective.index] as f64;
+            match objective.direction {
+                Direction::Maximised => -value,
+                Direction::Minimised => value,
+            }
+        })
+        .collect()
+}
+
+/// Values scaled by each objective's `min..=max` so that 0 is the best bound and 1 the worst.
+fn get_normalised(objectives: &[Objective], values: &[f32]) -> Vec<f64> {
+    objectives
+        .iter()
+        .map(|objective| {
+            let value = values[objective.index] as f64;
+            let (min, max) = (objective.min as f64, objective.max as f64);
+            let range = match max - min {
+                range if range > 0.0 => range,
+                _ => 1.0,
+            };
+            match objective.direction {
+                Direction::Maximised => (max - value) / range,
+                Direction::Minimised => (value - min) / range,
+            }
+        })
+        .collect()
+}
+
+fn get_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+fn get_points(objectives: &[Objective], items: &[ModelItem]) -> Vec<Vec<f64>> {
+    items
+        .iter()
+        .map(|item| get_minimised(objectives, &item.values))
+        .collect()
+}
+
+fn weakly_dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a <= b)
+}
+
+/// Drops points weakly dominated by another, keeping one of any duplicates.
+fn get_non_dominated(points: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    let mut front: Vec<Vec<f64>> = vec![];
+    for point in points {
+        if front.iter().any(|other| weakly_dominates(other, &point)) {
+            continue;
+        }
+        front.retain(|other| !weakly_dominates(&point, other));
+        front.push(point);
+    }
+    front
+}
@@ -0,0 +1,358 @@
+use std::thread;
+
+use rand::{prelude::SliceRandom, Rng};
+
+use crate::error::Spea2Error;
+use crate::evolve;
+use crate::model::{Model, ModelItem, Spea2Model};
+use crate::random;
+
+#[derive(Debug, Clone)]
+pub enum Topology {
+    Ring,
+    FullyConnected,
+    Random,
+}
+
+#[derive(Debug, Clone)]
+pub enum EmigrantPolicy {
+    Best,
+    Random,
+}
+
+#[derive(Debug, Clone)]
+pub enum ImmigrantPolicy {
+    ReplaceWorst,
+    ReplaceRandom,
+}
+
+#[derive(Debug, Clone)]
+pub struct MigrationConfig {
+    pub topology: Topology,
+    pub interval: usize,
+    pub size: usize,
+    pub emigrant_policy: EmigrantPolicy,
+    pub immigrant_policy: ImmigrantPolicy,
+}
+
+/// A single SPEA2 population evolving alongside the other islands.
+pub struct Island<S: Spea2Model> {
+    pub spea2_model: S,
+    pub model: Model,
+}
+
+impl<S: Spea2Model> Island<S> {
+    pub fn new(spea2_model: S) -> Self {
+        let model = spea2_model.get_model();
+        Self { spea2_model, model }
+    }
+
+    fn evolve(&mut self, generations: usize) -> Result<(), Spea2Error> {
+        let mut mutation = self.spea2_model.get_mutation_operator();
+        (0..generations).try_for_each(|_| evolve(&mut self.model, &mut mutation).map(|_| ()))
+    }
+}
+
+/// Evolves every island in its own thread, exchanging archive members every `config.interval` generations.
+/// Island threads are seeded from the calling thread's generator, so seeded runs stay reproducible.
+pub fn run_islands<S: Spea2Model + Send>(
+    islands: &mut [Island<S>],
+    config: &MigrationConfig,
+    generations: usize,
+) -> Result<(), Spea2Error> {
+    let interval = match config.interval {
+        0 => generations,
+        interval => interval,
+    };
+    let mut remaining = generations;
+
+    while remaining > 0 {
+        let epoch = interval.min(remaining);
+        let seeds: Vec<u64> = islands.iter().map(|_| random::get_rng().gen()).collect();
+        thread::scope(|scope| {
+            islands
+                .iter_mut()
+                .zip(seeds)
+                .map(|(island, seed)| {
+                    scope.spawn(move || {
+                        random::seed(seed);
+                        island.evolve(epoch)
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .try_for_each(|handle| handle.join().unwrap())
+        })?;
+        remaining -= epoch;
+        if remaining > 0 {
+            migrate(islands, config);
+        }
+    }
+    Ok(())
+}
+
+fn migrate<S: Spea2Model>(islands: &mut [Island<S>], config: &MigrationConfig) {
+    let mut rng = random::get_rng();
+    let len = islands.len();
+    let mut outgoing: Vec<Vec<usize>> = vec![vec![]; len];
+    let mut incoming: Vec<Vec<(usize, ModelItem)>> = vec![vec![]; len];
+    for from in 0..len {
+        for to in get_destinations(&config.topology, from, len, &mut rng) {
+            let archive = &islands[from].model.archive;
+            select_emigrants(archive, &config.emigrant_policy, config.size, &mut rng)
+                .into_iter()
+                .for_each(|i| {
+                    outgoing[from].push(i);
+                    incoming[to].push((from, archive[i].clone()));
+                });
+        }
+    }
+
+    incoming
+        .into_iter()
+        .enumerate()
+        .for_each(|(to, immigrants)| {
+            let slots = get_slots(
+                &islands[to].model.archive,
+                &outgoing[to],
+                &config.immigrant_policy,
+                &mut rng,
+            );
+            place_immigrants(islands, to, immigrants, slots);
+        });
+}
+
+fn get_destinations(
+    topology: &Topology,
+    from: usize,
+    len: usize,
+    rng: &mut impl Rng,
+) -> Vec<usize> {
+    if len < 2 {
+        return vec![];
+    }
+    match topology {
+        Topology::Ring => vec![(from + 1) % len],
+        Topology::FullyConnected => (0..len).filter(|&to| to != from).collect(),
+        Topology::Random => {
+            let to = rng.gen_range(0..len - 1);
+            vec![if to >= from { to + 1 } else { to }]
+        }
+    }
+}
+
+/// Archive positions of the items to send.
+fn select_emigrants(
+    archive: &[ModelItem],
+    policy: &EmigrantPolicy,
+    size: usize,
+    rng: &mut impl Rng,
+) -> Vec<usize> {
+    let mut positions: Vec<usize> = (0..archive.len()).collect();
+    match policy {
+        EmigrantPolicy::Best => {
+            positions.sort_by(|&a, &b| archive[a].fitness.total_cmp(&archive[b].fitness));
+            positions.truncate(size);
+            positions
+        }
+        EmigrantPolicy::Random => positions.choose_multiple(rng, size).cloned().collect(),
+    }
+}
+
+/// Archive positions immigrants may replace, most replaceable first. Emigrants are kept, as their
+/// custom data may not have been copied to their destinations yet.
+fn get_slots(
+    archive: &[ModelItem],
+    emigrants: &[usize],
+    policy: &ImmigrantPolicy,
+    rng: &mut impl Rng,
+) -> Vec<usize> {
+    let mut slots: Vec<usize> = (0..archive.len())
+        .filter(|i| !emigrants.contains(i))
+        .collect();
+    match policy {
+        ImmigrantPolicy::ReplaceWorst => {
+            slots.sort_by(|&a, &b| archive[b].fitness.total_cmp(&archive[a].fitness))
+        }
+        ImmigrantPolicy::ReplaceRandom => slots.shuffle(rng),
+    }
+    slots
+}
+
+/// Places immigrants from all sources into the archive of island `to`, one per slot, dropping any
+/// beyond the slots available so the archive keeps its size. Each immigrant takes over the custom
+/// data of the member it replaces, and its fitness is reset as it is not comparable across islands.
+fn place_immigrants<S: Spea2Model>(
+    islands: &mut [Island<S>],
+    to: usize,
+    immigrants: Vec<(usize, ModelItem)>,
+    slots: Vec<usize>,
+) {
+    immigrants
+        .into_iter()
+        .zip(slots)
+        .for_each(|((from, mut immigrant), slot)| {
+            let (source, destination) = get_island_pair(islands, from, to);
+            let archive = &mut destination.model.archive;
+            let from = immigrant.custom_data_index;
+            immigrant.custom_data_index = archive[slot].custom_data_index;
+            immigrant.fitness = 0.0;
+            destination
+                .spea2_model
+                .migrate(&source.spea2_model, from, immigrant.custom_data_index);
+            archive[slot] = immigrant;
+        });
+}
+
+fn get_island_pair<S: Spea2Model>(
+    islands: &mut [Island<S>],
+    from: usize,
+    to: usize,
+) -> (&Island<S>, &mut Island<S>) {
+    if from < to {
+        let (left, right) = islands.split_at_mut(to);
+        (&left[from], &mut right[0])
+    } else {
+        let (left, right) = islands.split_at_mut(from);
+        (&right[0], &mut left[to])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{self, MOCK_POPULATION_COUNT};
+
+    fn get_config(topology: Topology) -> MigrationConfig {
+        MigrationConfig {
+            topology,
+            interval: 5,
+            size: 2,
+            emigrant_policy: EmigrantPolicy::Best,
+            immigrant_policy: ImmigrantPolicy::ReplaceWorst,
+        }
+    }
+
+    #[test]
+    fn island_get_destinations() {
+        let mut rng = random::get_rng();
+        assert_eq!(get_destinations(&Topology::Ring, 0, 3, &mut rng), [1]);
+        assert_eq!(get_destinations(&Topology::Ring, 2, 3, &mut rng), [0]);
+        assert_eq!(
+            get_destinations(&Topology::FullyConnected, 1, 3, &mut rng),
+            [0, 2]
+        );
+        (0..100).for_each(|_| {
+            let to = get_destinations(&Topology::Random, 1, 3, &mut rng);
+            assert_eq!(to.len(), 1);
+            assert_ne!(to[0], 1);
+        });
+        assert!(get_destinations(&Topology::Ring, 0, 1, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn island_select_emigrants_best() {
+        let mut rng = random::get_rng();
+        let mut archive = mocks::get_non_dominated();
+        archive.reverse();
+        let emigrants = select_emigrants(&archive, &EmigrantPolicy::Best, 2, &mut rng);
+        assert_eq!(emigrants, [3, 2]);
+    }
+
+    #[test]
+    fn island_place_immigrants_replace_worst() {
+        let mut rng = random::get_rng();
+        let mut islands: Vec<Island<_>> = (0..2)
+            .map(|_| Island::new(mocks::get_spea2model()))
+            .collect();
+        islands[1].model.archive = mocks::get_non_dominated();
+        let immigrants = mocks::get_dominated()[..2]
+            .iter()
+            .map(|item| (0, item.clone()))
+            .collect();
+
+        let slots = get_slots(
+            &islands[1].model.archive,
+            &[],
+            &ImmigrantPolicy::ReplaceWorst,
+            &mut rng,
+        );
+        place_immigrants(&mut islands, 1, immigrants, slots);
+
+        let archive = &islands[1].model.archive;
+        let values: Vec<Vec<f32>> = archive.iter().map(|i| i.values.clone()).collect();
+        assert_eq!(values, [[5.0, 0.0], [0.0, 12.0], [4.0, 0.0], [0.0, 0.0]]);
+        let fitness: Vec<f32> = archive.iter().map(|i| i.fitness).collect();
+        assert_eq!(fitness, [1.0, 1.1, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn island_place_immigrants_keeps_archive_size() {
+        let mut rng = random::get_rng();
+        let mut islands: Vec<Island<_>> = (0..2)
+            .map(|_| Island::new(mocks::get_spea2model()))
+            .collect();
+        islands[1].model.archive = mocks::get_non_dominated()[..1].to_vec();
+        let immigrants = mocks::get_dominated()
+            .into_iter()
+            .map(|item| (0, item))
+            .collect();
+
+        let slots = get_slots(
+            &islands[1].model.archive,
+            &[],
+            &ImmigrantPolicy::ReplaceRandom,
+            &mut rng,
+        );
+        place_immigrants(&mut islands, 1, immigrants, slots);
+
+        assert_eq!(islands[1].model.archive.len(), 1);
+    }
+
+    #[test]
+    fn island_migrate_fully_connected() {
+        let mut islands: Vec<Island<_>> = (0..3)
+            .map(|_| {
+                let mut island = Island::new(mocks::get_spea2model());
+                island.model.archive = island.model.population.clone();
+                island
+                    .model
+                    .archive
+                    .iter_mut()
+                    .enumerate()
+                    .for_each(|(i, item)| item.fitness = (i + 1) as f32);
+                island
+            })
+            .collect();
+
+        migrate(&mut islands, &get_config(Topology::FullyConnected));
+
+        islands.iter().for_each(|island| {
+            let archive = &island.model.archive;
+            assert_eq!(archive.len(), MOCK_POPULATION_COUNT);
+            let immigrants = archive.iter().filter(|item| item.fitness == 0.0).count();
+            assert_eq!(immigrants, 4);
+            assert!(archive.iter().take(2).all(|item| item.fitness > 0.0));
+            archive.iter().for_each(|item| {
+                let index = item.custom_data_index.unwrap();
+                assert_eq!(item.values, island.spea2_model.custom_data[index].values);
+            });
+        });
+    }
+
+    #[test]
+    fn island_run_islands() {
+        let mut islands: Vec<Island<_>> = (0..3)
+            .map(|_| Island::new(mocks::get_spea2model()))
+            .collect();
+
+        [Topology::Ring, Topology::FullyConnected, Topology::Random]
+            .into_iter()
+            .for_each(|topology| {
+                run_islands(&mut islands, &get_config(topology), 12).unwrap();
+                islands.iter().for_each(|island| {
+                    assert_eq!(island.model.archive.len(), MOCK_POPULATION_COUNT);
+                });
+            });
+    }
+}
@@ -1,8 +1,11 @@
 use rand::Rng;
 
 use crate::model::{
-    Direction, Distance, Model, ModelItem, MutationOperator, Objective, Spea2Model,
+    BoundsPolicy, Direction, Distance, Genome, Model, ModelItem, MutationOperator, Objective,
+    Operators, Spea2Model, Variation,
 };
+use crate::operators::real::{self, Bounds};
+use crate::random;
 
 pub const MOCK_MIN_OBJECTIVE_VALUE: f32 = 0.0;
 pub const MOCK_MAX_OBJECTIVE_VALUE: f32 = 100.0;
@@ -16,6 +19,7 @@ fn get_objectives() -> Vec<Objective> {
             min: MOCK_MIN_OBJECTIVE_VALUE,
             max: MOCK_MAX_OBJECTIVE_VALUE,
             index: 0,
+            bounds_policy: BoundsPolicy::Ignore,
         },
         Objective {
             name: "mock_objective_minimised".to_string(),
@@ -23,12 +27,13 @@ fn get_objectives() -> Vec<Objective> {
             min: MOCK_MIN_OBJECTIVE_VALUE,
             max: MOCK_MAX_OBJECTIVE_VALUE,
             index: 1,
+            bounds_policy: BoundsPolicy::Ignore,
         },
     ];
     objectives
 }
 pub fn get_rnd_model_item_vec(objectives: &[Objective]) -> Vec<ModelItem> {
-    let mut rng = rand::thread_rng();
+    let mut rng = random::get_rng();
     (0..MOCK_POPULATION_COUNT)
         .map(|_| {
             ModelItem::new(
@@ -96,6 +101,62 @@ pub fn get_model_with_archive() -> Model {
     model
 }
 
+pub fn get_model_with_non_finite_values() -> Model {
+    let objectives = get_objectives();
+    let population = vec![
+        ModelItem::new(vec![1.0, 1.0], Some(0)),
+        ModelItem::new(vec![2.0, f32::INFINITY], Some(0)),
+        ModelItem::new(vec![f32::NAN, 3.0], Some(0)),
+    ];
+    Model::new(objectives, population)
+}
+
+pub fn get_model_with_out_of_bounds_values() -> Model {
+    let objectives = get_objectives();
+    let population = vec![
+        ModelItem::new(vec![1.0, 1.0], Some(0)),
+        ModelItem::new(vec![150.0, -20.0], Some(0)),
+    ];
+    Model::new(objectives, population)
+}
+
+pub fn get_real_bounds() -> Vec<Bounds> {
+    vec![Bounds::new(0.0, 1.0), Bounds::new(0.0, 1.0)]
+}
+
+pub fn evaluate_real_genome(_: &[Objective], item: &mut ModelItem) {
+    if let Genome::Real(x) = &item.genome {
+        let value = (x[0] * MOCK_MAX_OBJECTIVE_VALUE as f64) as f32;
+        let cost = ((1.0 - x[0] + x[1]) * MOCK_MAX_OBJECTIVE_VALUE as f64 / 2.0) as f32;
+        item.values = vec![value, cost];
+    }
+}
+
+pub fn get_model_with_real_genomes() -> Model {
+    let objectives = get_objectives();
+    let mut rng = random::get_rng();
+    let population = (0..MOCK_POPULATION_COUNT)
+        .map(|_| {
+            let mut item = ModelItem::new(vec![], None);
+            item.genome = Genome::Real(vec![rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0)]);
+            evaluate_real_genome(&objectives, &mut item);
+            item
+        })
+        .collect();
+    Model::new(objectives, population)
+}
+
+pub fn get_real_operators<'a>() -> Operators<'a> {
+    Operators {
+        variation: Variation::Genetic,
+        crossover: Some(real::get_sbx_crossover_operator(get_real_bounds(), 15.0)),
+        mutation: real::get_polynomial_mutation_operator(get_real_bounds(), 20.0, 0.5),
+        portfolio: None,
+        repair: None,
+        evaluation: Some(Box::new(evaluate_real_genome)),
+    }
+}
+
 pub fn get_model_item_with_fitness(values: Vec<f32>, fitness: f32) -> ModelItem {
     let mut model_item = ModelItem::new(values, Some(0));
     model_item.fitness = fitness;
@@ -155,7 +216,7 @@ pub struct MockCustomData {
 }
 impl MockCustomData {
     pub fn default() -> MockCustomData {
-        let mut rng = rand::thread_rng();
+        let mut rng = random::get_rng();
         MockCustomData {
             values: vec![
                 rng.gen_range(MOCK_MIN_OBJECTIVE_VALUE..MOCK_MAX_OBJECTIVE_VALUE),
@@ -203,7 +264,7 @@ impl Spea2Model for MockSpea2Model {
 
     fn get_mutation_operator(&mut self) -> MutationOperator {
         let mut_op = move |objectives: &[Objective], item: &mut ModelItem| {
-            let mut rng = rand::thread_rng();
+            let mut rng = random::get_rng();
             let index = item.custom_data_index.unwrap();
             let custom_data_item = self.custom_data.get_mut(index).unwrap();
 
@@ -214,4 +275,25 @@ impl Spea2Model for MockSpea2Model {
         };
         Box::new(mut_op)
     }
+
+    fn evaluate(&mut self, _: &[Objective], item: &mut ModelItem) {
+        let index = item.custom_data_index.unwrap();
+        item.values = self.custom_data[index].values.clone();
+    }
+
+    fn get_random_item(&mut self, _: &[Objective]) -> Option<ModelItem> {
+        let custom_data_item = MockCustomData::default();
+        let item = ModelItem::new(
+            custom_data_item.values.clone(),
+            Some(self.custom_data.len()),
+        );
+        self.custom_data.push(custom_data_item);
+        Some(item)
+    }
+
+    fn migrate(&mut self, source: &Self, from: Option<usize>, to: Option<usize>) {
+        if let (Some(from), Some(to)) = (from, to) {
+            self.custom_data[to].values = source.custom_data[from].values.clone();
+        }
+    }
 }
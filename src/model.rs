@@ -1,8 +1,63 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::constants::MUTATION_RATE;
+use crate::indicators::hypervolume;
+use crate::observer::Indicator;
+use crate::operators::real::Bounds;
+use crate::portfolio::Portfolio;
+use crate::steady_state::ArchiveCache;
+
 pub type MutationOperator<'a> = Box<dyn FnMut(&[Objective], &mut ModelItem) + 'a>;
+pub type CrossoverOperator<'a> = Box<dyn FnMut(&[Objective], &mut ModelItem, &mut ModelItem) + 'a>;
+pub type RepairOperator<'a> = Box<dyn FnMut(&[Objective], &mut ModelItem) + 'a>;
+pub type EvaluationOperator<'a> = Box<dyn FnMut(&[Objective], &mut ModelItem) + 'a>;
+
+/// The variation pipeline used by `evolve_with`. Items changed by crossover or mutation are passed
+/// to `repair` and then to `evaluation`; leave `evaluation` as `None` when the operators keep the
+/// values up to date themselves. A `portfolio`, when set, is used in place of `crossover` and
+/// `mutation` for genetic variation.
+pub struct Operators<'a> {
+    pub variation: Variation,
+    pub crossover: Option<CrossoverOperator<'a>>,
+    pub mutation: MutationOperator<'a>,
+    pub portfolio: Option<Portfolio<'a>>,
+    pub repair: Option<RepairOperator<'a>>,
+    pub evaluation: Option<EvaluationOperator<'a>>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub enum Variation {
+    /// Crossover, if any, followed by mutation.
+    #[default]
+    Genetic,
+    /// DE/rand/1/bin on `Genome::Real` genomes in place of crossover and mutation.
+    DifferentialEvolution {
+        f: f64,
+        cr: f64,
+        bounds: Vec<Bounds>,
+    },
+}
 
 pub trait Spea2Model {
     fn get_model(&self) -> Model;
     fn get_mutation_operator(&mut self) -> MutationOperator<'_>;
+
+    /// Re-evaluates `item` against the current state of the environment.
+    fn evaluate(&mut self, _objectives: &[Objective], _item: &mut ModelItem) {}
+
+    /// A fresh random item, injected as an immigrant when the environment changes.
+    fn get_random_item(&mut self, _objectives: &[Objective]) -> Option<ModelItem> {
+        None
+    }
+
+    /// Called when an item migrates in from another island, so the custom data at `from` in `source`
+    /// can be copied into `to`, the custom data index the immigrant takes over.
+    fn migrate(&mut self, _source: &Self, _from: Option<usize>, _to: Option<usize>)
+    where
+        Self: Sized,
+    {
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -13,6 +68,22 @@ pub struct Model {
     pub mating_pool: Vec<ModelItem>,
     pub population_size: usize,
     pub neighbourhood_size: usize,
+    pub mutation_schedule: MutationSchedule,
+    pub effective_mutation_rate: f64,
+    pub generation: usize,
+    pub non_finite_policy: NonFinitePolicy,
+    pub quarantine: Vec<ModelItem>,
+    pub duplicate_detection: DuplicateDetection,
+    pub bounds_violations: Vec<usize>,
+    /// Standard errors by which two sampled means must differ before one is considered better.
+    pub dominance_confidence: f32,
+    /// Items whose values were computed or recomputed, counted across generations.
+    pub evaluations: usize,
+    /// When set, `GenerationStats` include the archive's hypervolume relative to this point.
+    pub reference_point: Option<Vec<f32>>,
+    /// Further indicators of the archive reported in `GenerationStats::indicators`.
+    pub indicators: Vec<Indicator>,
+    pub(crate) archive_cache: ArchiveCache,
     objective_sort_index: usize,
 }
 impl Model {
@@ -50,6 +121,10 @@ impl Model {
             .collect()
     }
     
+    pub fn get_archive_hypervolume(&self, reference: &[f32]) -> f64 {
+        hypervolume::hypervolume(&self.objectives, &self.archive, reference)
+    }
+
     pub fn get_average_fitness(&self) -> f32 {
         let len = self.archive.len();
         self.archive.iter().map(|item| item.fitness).sum::<f32>() / len as f32
@@ -63,22 +138,103 @@ pub struct Objective {
     pub min: f32,
     pub max: f32,
     pub index: usize,
+    pub bounds_policy: BoundsPolicy,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct ModelItem {
     pub values: Vec<f32>,
+    pub genome: Genome,
     pub fitness: f32,
     pub custom_data_index: Option<usize>,
+    pub mutation_rate: Option<f64>,
+    pub genome_hash: Option<u64>,
+    pub variances: Vec<f32>,
+    pub samples: usize,
+    /// The portfolio operator that produced the item, until it is credited.
+    pub operator_index: Option<usize>,
 }
 
 impl ModelItem {
     pub fn new(values: Vec<f32>, custom_data_index: Option<usize>) -> Self {
         Self {
             values,
+            genome: Genome::None,
             fitness: 0.0,
             custom_data_index,
+            mutation_rate: None,
+            genome_hash: None,
+            variances: vec![],
+            samples: 1,
+            operator_index: None,
+        }
+    }
+
+    pub fn get_standard_error(&self, index: usize) -> f32 {
+        match self.variances.get(index) {
+            Some(variance) if self.samples > 1 => (variance / self.samples as f32).sqrt(),
+            _ => 0.0,
+        }
+    }
+
+    /// Pareto dominance: at least as good in every objective and better in at least one.
+    pub fn dominates(&self, other: &ModelItem, objectives: &[Objective]) -> bool {
+        let mut better = false;
+        for objective in objectives {
+            let (a, b) = (self.values[objective.index], other.values[objective.index]);
+            let (a, b) = match objective.direction {
+                Direction::Maximised => (a, b),
+                Direction::Minimised => (b, a),
+            };
+            if a < b {
+                return false;
+            }
+            better |= a > b;
         }
+        better
+    }
+
+    /// Forgets previous samples once the item has changed, keeping its current values as the first.
+    pub fn reset_samples(&mut self) {
+        self.samples = 1;
+        self.variances.clear();
+    }
+}
+
+/// Decision variables for the built-in operators. Items whose data lives elsewhere, found through
+/// `custom_data_index`, have no genome.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum Genome {
+    #[default]
+    None,
+    Real(Vec<f64>),
+    Binary(Vec<bool>),
+    /// An ordering of the genes `0..len`; the crossover operators rely on these being the labels.
+    Permutation(Vec<usize>),
+}
+
+impl Genome {
+    /// The number of genes, or `None` when there is no genome.
+    pub fn get_len(&self) -> Option<usize> {
+        match self {
+            Genome::None => None,
+            Genome::Real(x) => Some(x.len()),
+            Genome::Binary(x) => Some(x.len()),
+            Genome::Permutation(x) => Some(x.len()),
+        }
+    }
+
+    /// A hash of the genes, or `None` when there is no genome.
+    pub fn get_hash(&self) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+        match self {
+            Genome::None => return None,
+            Genome::Real(x) => x.iter().for_each(|gene| gene.to_bits().hash(&mut hasher)),
+            Genome::Binary(x) => x.hash(&mut hasher),
+            Genome::Permutation(x) => x.hash(&mut hasher),
+        }
+        std::mem::discriminant(self).hash(&mut hasher);
+        Some(hasher.finish())
     }
 }
 
@@ -95,6 +251,94 @@ impl Distance {
     }
 }
 
+#[derive(Debug, Clone)]
+pub enum MutationSchedule {
+    Constant(f64),
+    LinearDecay {
+        start: f64,
+        end: f64,
+        generations: usize,
+    },
+    ExponentialDecay {
+        start: f64,
+        decay: f64,
+    },
+    /// 1/L, where L is the length of the genome being mutated. The given length is used for items
+    /// with `Genome::None`, whose genes are kept elsewhere.
+    InverseLength(usize),
+    /// Each item carries its own rate, perturbed log-normally by `learning_rate` before every use.
+    SelfAdaptive {
+        initial: f64,
+        learning_rate: f64,
+        min: f64,
+        max: f64,
+    },
+}
+
+impl Default for MutationSchedule {
+    fn default() -> Self {
+        MutationSchedule::Constant(MUTATION_RATE)
+    }
+}
+
+impl MutationSchedule {
+    pub fn get_rate(&self, generation: usize) -> f64 {
+        match *self {
+            MutationSchedule::Constant(rate) => rate,
+            MutationSchedule::LinearDecay {
+                start,
+                end,
+                generations,
+            } => {
+                let progress = (generation as f64 / generations.max(1) as f64).min(1.0);
+                start + (end - start) * progress
+            }
+            MutationSchedule::ExponentialDecay { start, decay } => {
+                start * (-decay * generation as f64).exp()
+            }
+            MutationSchedule::InverseLength(length) => 1.0 / length.max(1) as f64,
+            MutationSchedule::SelfAdaptive { initial, .. } => initial,
+        }
+    }
+}
+
+/// What to do with population items that come back from evaluation with NaN or infinite values.
+#[derive(Debug, Default, Clone)]
+pub enum NonFinitePolicy {
+    #[default]
+    Fail,
+    Reject,
+    /// Replace each non-finite value with the worst bound of its objective.
+    Penalise,
+    /// Move the item into `Model::quarantine` for inspection.
+    Quarantine,
+}
+
+#[derive(Debug, Default, Clone)]
+pub enum DuplicateDetection {
+    #[default]
+    Disabled,
+    Exact,
+    /// Items whose values all lie within the tolerance of each other in objective space.
+    Tolerance(f32),
+    /// Items sharing a `ModelItem::genome_hash`. The hash is computed from the genome of items that
+    /// have one; models keeping their data elsewhere set it themselves.
+    GenomeHash,
+}
+
+/// How evaluated values outside an objective's `min..=max` are handled. Violations are counted
+/// in `Model::bounds_violations` whatever the policy.
+#[derive(Debug, Default, Clone)]
+pub enum BoundsPolicy {
+    #[default]
+    Ignore,
+    Clamp,
+    /// Move the value beyond the worst bound by the amount it was out of range.
+    Penalise,
+    Reject,
+    Expand,
+}
+
 #[derive(Debug, Clone)]
 pub enum Direction {
     Maximised,
@@ -103,6 +347,7 @@ pub enum Direction {
 
 #[cfg(test)]
 mod tests {
+    use super::{ModelItem, MutationSchedule};
     use crate::mocks::{self, MOCK_POPULATION_COUNT};
 
     #[test]
@@ -129,4 +374,38 @@ mod tests {
         let expected = MOCK_POPULATION_COUNT as f32 / 2.0;
         assert_eq!(average_values, vec![expected, expected]);
     }
+
+    #[test]
+    fn model_item_dominates() {
+        let objectives = mocks::get_model_basic().objectives;
+        let item = ModelItem::new(vec![5.0, 5.0], None);
+
+        assert!(ModelItem::new(vec![6.0, 5.0], None).dominates(&item, &objectives));
+        assert!(ModelItem::new(vec![5.0, 4.0], None).dominates(&item, &objectives));
+        assert!(!ModelItem::new(vec![5.0, 5.0], None).dominates(&item, &objectives));
+        assert!(!ModelItem::new(vec![6.0, 6.0], None).dominates(&item, &objectives));
+    }
+
+    #[test]
+    fn model_mutation_schedule_get_rate() {
+        let linear = MutationSchedule::LinearDecay {
+            start: 1.0,
+            end: 0.0,
+            generations: 4,
+        };
+        assert_eq!(linear.get_rate(0), 1.0);
+        assert_eq!(linear.get_rate(2), 0.5);
+        assert_eq!(linear.get_rate(4), 0.0);
+        assert_eq!(linear.get_rate(10), 0.0);
+
+        let exponential = MutationSchedule::ExponentialDecay {
+            start: 0.5,
+            decay: 0.1,
+        };
+        assert_eq!(exponential.get_rate(0), 0.5);
+        assert!(exponential.get_rate(10) < exponential.get_rate(5));
+
+        assert_eq!(MutationSchedule::InverseLength(20).get_rate(7), 0.05);
+        assert_eq!(MutationSchedule::default().get_rate(100), 0.1);
+    }
 }
@@ -1,26 +1,123 @@
+use std::f64::consts::PI;
+
 use rand::Rng;
 
-use crate::{
-    constants::MUTATION_RATE,
-    model::{Model, MutationOperator},
-};
+use crate::model::{Model, ModelItem, MutationOperator, MutationSchedule};
+use crate::random::{self, SeededRng};
 
-pub fn mutate(model: &mut Model, mutation: &mut MutationOperator) {
-    let mut rng = rand::thread_rng();
+pub fn mutate(model: &mut Model, mutation: &mut MutationOperator) -> Vec<bool> {
+    let mut rng = random::get_rng();
     let objectives = &model.objectives;
-    model.mating_pool.iter_mut().for_each(|item| {
-        if rng.gen_bool(MUTATION_RATE) {
-            mutation(objectives, item);
+    let schedule = &model.mutation_schedule;
+    let generation = model.generation;
+    let mut total_rate = 0.0;
+    let mutated = model
+        .mating_pool
+        .iter_mut()
+        .map(|item| {
+            let rate = get_item_rate(schedule, generation, item, &mut rng);
+            total_rate += rate;
+            let mutate = rng.gen_bool(rate);
+            if mutate {
+                mutation(objectives, item);
+                item.reset_samples();
+            }
+            mutate
+        })
+        .collect();
+    if !model.mating_pool.is_empty() {
+        model.effective_mutation_rate = total_rate / model.mating_pool.len() as f64;
+    }
+    mutated
+}
+
+fn get_item_rate(
+    schedule: &MutationSchedule,
+    generation: usize,
+    item: &mut ModelItem,
+    rng: &mut SeededRng,
+) -> f64 {
+    match *schedule {
+        MutationSchedule::SelfAdaptive {
+            initial,
+            learning_rate,
+            min,
+            max,
+        } => {
+            let inherited = item.mutation_rate.unwrap_or(initial);
+            let rate =
+                (inherited * (learning_rate * get_standard_normal(rng)).exp()).clamp(min, max);
+            item.mutation_rate = Some(rate);
+            rate
+        }
+        MutationSchedule::InverseLength(length) => {
+            1.0 / item.genome.get_len().unwrap_or(length).max(1) as f64
         }
-    });
+        _ => schedule.get_rate(generation).clamp(0.0, 1.0),
+    }
+}
+
+fn get_standard_normal(rng: &mut SeededRng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::{
         mocks::{self},
-        model::{Direction, Spea2Model},
+        model::{Direction, Genome, Spea2Model},
     };
+
+    #[test]
+    fn mutation_get_item_rate_self_adaptive() {
+        let mut rng = random::get_rng();
+        let schedule = MutationSchedule::SelfAdaptive {
+            initial: 0.2,
+            learning_rate: 0.5,
+            min: 0.05,
+            max: 0.5,
+        };
+        let mut item = ModelItem::new(vec![0.0, 0.0], Some(0));
+
+        (0..100).for_each(|_| {
+            let rate = get_item_rate(&schedule, 0, &mut item, &mut rng);
+            assert!((0.05..=0.5).contains(&rate));
+            assert_eq!(item.mutation_rate, Some(rate));
+        });
+    }
+
+    #[test]
+    fn mutation_get_item_rate_inverse_length() {
+        let mut rng = random::get_rng();
+        let schedule = MutationSchedule::InverseLength(100);
+        let mut item = ModelItem::new(vec![0.0, 0.0], None);
+
+        assert_eq!(get_item_rate(&schedule, 0, &mut item, &mut rng), 0.01);
+        item.genome = Genome::Binary(vec![false; 4]);
+        assert_eq!(get_item_rate(&schedule, 0, &mut item, &mut rng), 0.25);
+        item.genome = Genome::Real(vec![]);
+        assert_eq!(get_item_rate(&schedule, 0, &mut item, &mut rng), 1.0);
+    }
+
+    #[test]
+    fn mutation_mutate_effective_rate() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut mutation = spea2_model.get_mutation_operator();
+        let mut model = mocks::get_model_with_mating_pool();
+        model.mutation_schedule = MutationSchedule::LinearDecay {
+            start: 1.0,
+            end: 0.0,
+            generations: 10,
+        };
+        model.generation = 5;
+
+        mutate(&mut model, &mut mutation);
+
+        assert_eq!(model.effective_mutation_rate, 0.5);
+    }
     #[test]
     fn mutation_perform_mutation() {
         let mut spea2_model = mocks::get_spea2model();
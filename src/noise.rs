@@ -0,0 +1,169 @@
+use crate::error::Spea2Error;
+use crate::evolve;
+use crate::model::{Model, ModelItem, Objective, Spea2Model};
+
+#[derive(Debug, Clone)]
+pub struct NoiseConfig {
+    /// Evaluations of every new population member.
+    pub samples: usize,
+    /// Copied to `Model::dominance_confidence`.
+    pub confidence: f32,
+    /// Extra evaluations shared out each generation among the least certain archive members.
+    pub resample_budget: usize,
+    pub max_samples: usize,
+}
+
+/// Evolves `model` with objectives that are re-sampled through `Spea2Model::evaluate`, so each
+/// item's values hold the mean of its samples and `variances` their spread.
+pub fn evolve_noisy<S: Spea2Model>(
+    spea2_model: &mut S,
+    model: &mut Model,
+    config: &NoiseConfig,
+    generations: usize,
+) -> Result<(), Spea2Error> {
+    model.dominance_confidence = config.confidence;
+    for _ in 0..generations {
+        let objectives = &model.objectives;
+        let mut evaluations = 0;
+        model.population.iter_mut().for_each(|item| {
+            let count = config.samples.saturating_sub(item.samples);
+            sample(spea2_model, objectives, item, count);
+            evaluations += count;
+        });
+        model.evaluations += evaluations;
+        resample_archive(spea2_model, model, config);
+
+        let mut mutation = spea2_model.get_mutation_operator();
+        evolve(model, &mut mutation)?;
+    }
+    Ok(())
+}
+
+pub fn sample<S: Spea2Model>(
+    spea2_model: &mut S,
+    objectives: &[Objective],
+    item: &mut ModelItem,
+    count: usize,
+) {
+    if item.variances.len() != item.values.len() {
+        item.variances = vec![0.0; item.values.len()];
+    }
+    for _ in 0..count {
+        let mut evaluated = item.clone();
+        spea2_model.evaluate(objectives, &mut evaluated);
+
+        let previous = item.samples.max(1);
+        item.samples = previous + 1;
+        let n = item.samples as f32;
+        for k in 0..item.values.len() {
+            let delta = evaluated.values[k] - item.values[k];
+            item.values[k] += delta / n;
+            let sum_of_squares = item.variances[k] * (previous - 1) as f32
+                + delta * (evaluated.values[k] - item.values[k]);
+            item.variances[k] = sum_of_squares / (n - 1.0);
+        }
+    }
+}
+
+fn resample_archive<S: Spea2Model>(spea2_model: &mut S, model: &mut Model, config: &NoiseConfig) {
+    for _ in 0..config.resample_budget {
+        let least_certain = model
+            .archive
+            .iter_mut()
+            .filter(|item| item.samples < config.max_samples)
+            .map(|item| {
+                let error = (0..item.values.len())
+                    .map(|k| item.get_standard_error(k))
+                    .fold(0.0, f32::max);
+                (error, item)
+            })
+            .max_by(|(a, _), (b, _)| a.total_cmp(b));
+        match least_certain {
+            Some((_, item)) => {
+                sample(spea2_model, &model.objectives, item, 1);
+                model.evaluations += 1;
+            }
+            None => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{self, MOCK_POPULATION_COUNT};
+    use crate::model::MutationOperator;
+
+    struct CountingModel {
+        count: f32,
+    }
+
+    impl Spea2Model for CountingModel {
+        fn get_model(&self) -> Model {
+            mocks::get_model_basic()
+        }
+
+        fn get_mutation_operator(&mut self) -> MutationOperator<'_> {
+            Box::new(|_, _| ())
+        }
+
+        fn evaluate(&mut self, _: &[Objective], item: &mut ModelItem) {
+            self.count += 1.0;
+            item.values = vec![self.count, 2.0 * self.count];
+        }
+    }
+
+    #[test]
+    fn noise_sample() {
+        let mut spea2_model = CountingModel { count: 1.0 };
+        let objectives = spea2_model.get_model().objectives;
+        let mut item = ModelItem::new(vec![1.0, 2.0], Some(0));
+
+        sample(&mut spea2_model, &objectives, &mut item, 3);
+
+        assert_eq!(item.samples, 4);
+        assert_eq!(item.values, [2.5, 5.0]);
+        assert!((item.variances[0] - 5.0 / 3.0).abs() < 1e-6);
+        assert!((item.variances[1] - 20.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn noise_resample_archive() {
+        let mut spea2_model = CountingModel { count: 0.0 };
+        let mut model = mocks::get_model_with_archive();
+        model.archive[3].samples = 2;
+        model.archive[3].variances = vec![4.0, 4.0];
+        let config = NoiseConfig {
+            samples: 1,
+            confidence: 1.0,
+            resample_budget: 3,
+            max_samples: 4,
+        };
+
+        resample_archive(&mut spea2_model, &mut model, &config);
+
+        assert_eq!(model.archive[3].samples, 4);
+        assert_eq!(
+            model.archive.iter().map(|item| item.samples).sum::<usize>(),
+            model.archive.len() + 4
+        );
+    }
+
+    #[test]
+    fn noise_evolve_noisy() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = spea2_model.get_model();
+        let config = NoiseConfig {
+            samples: 3,
+            confidence: 1.0,
+            resample_budget: 2,
+            max_samples: 5,
+        };
+
+        evolve_noisy(&mut spea2_model, &mut model, &config, 5).unwrap();
+
+        assert_eq!(model.dominance_confidence, 1.0);
+        assert_eq!(model.archive.len(), MOCK_POPULATION_COUNT);
+        assert!(model.archive.iter().all(|item| item.samples >= 3));
+    }
+}
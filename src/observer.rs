@@ -0,0 +1,181 @@
+use std::time::{Duration, Instant};
+
+use crate::indicators::{distance, diversity, hypervolume};
+use crate::model::{Direction, Model, ModelItem, Objective};
+
+/// Hooks called by `evolve_observed` and `evolve_with_observed` at each phase of a generation.
+/// `()` is the observer that does nothing.
+pub trait Observer {
+    /// After the population has been validated, before fitness is assigned.
+    fn before_fitness(&mut self, _model: &Model) {}
+    /// Once the new archive has been selected.
+    fn after_selection(&mut self, _model: &Model) {}
+    /// Once the next population has been bred.
+    fn after_reproduction(&mut self, _model: &Model) {}
+    fn generation_end(&mut self, _model: &Model, _stats: &GenerationStats) {}
+}
+
+impl Observer for () {}
+
+#[derive(Debug, Default, Clone)]
+pub struct PhaseTimings {
+    /// Duplicate elimination and validation.
+    pub validation: Duration,
+    pub fitness: Duration,
+    pub selection: Duration,
+    pub reproduction: Duration,
+}
+
+impl PhaseTimings {
+    pub fn get_total(&self) -> Duration {
+        self.validation + self.fitness + self.selection + self.reproduction
+    }
+}
+
+/// An indicator of the archive, computed at the end of every generation when listed in
+/// `Model::indicators`. Those taking a reference front measure against it.
+#[derive(Debug, Clone)]
+pub enum Indicator {
+    Spacing,
+    Spread(Vec<Vec<f32>>),
+    GenerationalDistance(Vec<Vec<f32>>),
+    InvertedGenerationalDistance(Vec<Vec<f32>>),
+}
+
+impl Indicator {
+    pub fn get_value(&self, objectives: &[Objective], items: &[ModelItem]) -> f64 {
+        match self {
+            Indicator::Spacing => diversity::spacing(objectives, items),
+            Indicator::Spread(front) => diversity::spread(objectives, items, front),
+            Indicator::GenerationalDistance(front) => {
+                distance::generational_distance(objectives, items, front)
+            }
+            Indicator::InvertedGenerationalDistance(front) => {
+                distance::inverted_generational_distance(objectives, items, front)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct GenerationStats {
+    pub generation: usize,
+    pub archive_size: usize,
+    pub non_dominated_count: usize,
+    /// The best archive value of each objective.
+    pub ideal: Vec<f32>,
+    /// The worst value of each objective among the non-dominated archive items.
+    pub nadir: Vec<f32>,
+    /// Set when the model has a `reference_point`.
+    pub hypervolume: Option<f64>,
+    /// The value of each of `Model::indicators`, in order.
+    pub indicators: Vec<f64>,
+    /// `Model::evaluations` at the end of the generation.
+    pub evaluations: usize,
+    pub effective_mutation_rate: f64,
+    pub bounds_violations: Vec<usize>,
+    pub timings: PhaseTimings,
+}
+
+impl GenerationStats {
+    pub fn new(model: &Model, timings: PhaseTimings) -> Self {
+        let non_dominated: Vec<&Vec<f32>> = model
+            .archive
+            .iter()
+            .filter(|item| item.fitness < 1.0)
+            .map(|item| &item.values)
+            .collect();
+        let (ideal, nadir) = model
+            .objectives
+            .iter()
+            .map(|objective| {
+                let values = || {
+                    model
+                        .archive
+                        .iter()
+                        .map(|item| item.values[objective.index])
+                };
+                let front = || non_dominated.iter().map(|values| values[objective.index]);
+                match objective.direction {
+                    Direction::Maximised => (
+                        values().fold(f32::NEG_INFINITY, f32::max),
+                        front().fold(f32::INFINITY, f32::min),
+                    ),
+                    Direction::Minimised => (
+                        values().fold(f32::INFINITY, f32::min),
+                        front().fold(f32::NEG_INFINITY, f32::max),
+                    ),
+                }
+            })
+            .unzip();
+
+        Self {
+            generation: model.generation,
+            archive_size: model.archive.len(),
+            non_dominated_count: non_dominated.len(),
+            ideal,
+            nadir,
+            hypervolume: model.reference_point.as_ref().map(|reference| {
+                hypervolume::hypervolume(&model.objectives, &model.archive, reference)
+            }),
+            indicators: model
+                .indicators
+                .iter()
+                .map(|indicator| indicator.get_value(&model.objectives, &model.archive))
+                .collect(),
+            evaluations: model.evaluations,
+            effective_mutation_rate: model.effective_mutation_rate,
+            bounds_violations: model.bounds_violations.clone(),
+            timings,
+        }
+    }
+}
+
+/// Runs `phase`, adding the time it took to `duration`.
+pub(crate) fn time<T>(duration: &mut Duration, phase: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = phase();
+    *duration += start.elapsed();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks;
+
+    #[test]
+    fn observer_generation_stats() {
+        let mut model = mocks::get_model_basic();
+        model.archive = vec![
+            mocks::get_model_item_with_fitness(vec![4.0, 2.0], 0.2),
+            mocks::get_model_item_with_fitness(vec![1.0, 0.0], 0.3),
+            mocks::get_model_item_with_fitness(vec![0.0, 5.0], 2.0),
+        ];
+        model.reference_point = Some(vec![0.0, 3.0]);
+        model.indicators = vec![
+            Indicator::Spacing,
+            Indicator::InvertedGenerationalDistance(vec![vec![4.0, 0.0]]),
+        ];
+        let timings = PhaseTimings {
+            fitness: Duration::from_millis(2),
+            selection: Duration::from_millis(1),
+            ..Default::default()
+        };
+
+        let stats = GenerationStats::new(&model, timings);
+
+        assert_eq!(stats.archive_size, 3);
+        assert_eq!(stats.non_dominated_count, 2);
+        assert_eq!(stats.ideal, [4.0, 0.0]);
+        assert_eq!(stats.nadir, [1.0, 2.0]);
+        assert_eq!(stats.hypervolume, Some(6.0));
+        assert_eq!(stats.indicators.len(), 2);
+        assert_eq!(
+            stats.indicators[0],
+            diversity::spacing(&model.objectives, &model.archive)
+        );
+        assert!(stats.indicators[1] > 0.0);
+        assert_eq!(stats.timings.get_total(), Duration::from_millis(3));
+    }
+}
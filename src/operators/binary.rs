@@ -0,0 +1,194 @@
+use rand::Rng;
+
+use crate::model::{CrossoverOperator, Genome, MutationOperator, RepairOperator};
+use crate::random::{self, SeededRng};
+
+pub fn get_bit_flip_mutation_operator<'a>(probability: f64) -> MutationOperator<'a> {
+    Box::new(move |_, item| {
+        if let Genome::Binary(bits) = &mut item.genome {
+            bit_flip(bits, probability, &mut random::get_rng());
+        }
+    })
+}
+
+pub fn get_one_point_crossover_operator<'a>() -> CrossoverOperator<'a> {
+    get_crossover_operator(one_point)
+}
+
+pub fn get_two_point_crossover_operator<'a>() -> CrossoverOperator<'a> {
+    get_crossover_operator(two_point)
+}
+
+pub fn get_uniform_crossover_operator<'a>() -> CrossoverOperator<'a> {
+    get_crossover_operator(uniform)
+}
+
+/// Repairs `Genome::Binary` genomes whose total weight exceeds `capacity` by dropping the set bits
+/// with the lowest priority first, e.g. the value to weight ratio of knapsack items. Bits beyond
+/// the end of `weights` or `priorities` are left as they are.
+pub fn get_greedy_repair_operator<'a>(
+    weights: Vec<f64>,
+    priorities: Vec<f64>,
+    capacity: f64,
+) -> RepairOperator<'a> {
+    Box::new(move |_, item| {
+        if let Genome::Binary(bits) = &mut item.genome {
+            greedy_repair(bits, &weights, &priorities, capacity);
+        }
+    })
+}
+
+fn get_crossover_operator<'a>(
+    crossover: fn(&mut [bool], &mut [bool], &mut SeededRng),
+) -> CrossoverOperator<'a> {
+    Box::new(move |_, p1, p2| {
+        if let (Genome::Binary(b1), Genome::Binary(b2)) = (&mut p1.genome, &mut p2.genome) {
+            crossover(b1, b2, &mut random::get_rng());
+        }
+    })
+}
+
+pub fn bit_flip(bits: &mut [bool], probability: f64, rng: &mut impl Rng) {
+    bits.iter_mut().for_each(|bit| {
+        if rng.gen_bool(probability) {
+            *bit = !*bit;
+        }
+    });
+}
+
+pub fn one_point(b1: &mut [bool], b2: &mut [bool], rng: &mut impl Rng) {
+    let len = b1.len().min(b2.len());
+    if len < 2 {
+        return;
+    }
+    let point = rng.gen_range(1..len);
+    b1[point..len].swap_with_slice(&mut b2[point..len]);
+}
+
+pub fn two_point(b1: &mut [bool], b2: &mut [bool], rng: &mut impl Rng) {
+    let len = b1.len().min(b2.len());
+    if len < 2 {
+        return;
+    }
+    let a = rng.gen_range(0..len);
+    let b = rng.gen_range(0..len);
+    let (start, end) = (a.min(b), a.max(b) + 1);
+    b1[start..end].swap_with_slice(&mut b2[start..end]);
+}
+
+pub fn uniform(b1: &mut [bool], b2: &mut [bool], rng: &mut impl Rng) {
+    b1.iter_mut().zip(b2.iter_mut()).for_each(|(a, b)| {
+        if rng.gen_bool(0.5) {
+            std::mem::swap(a, b);
+        }
+    });
+}
+
+pub fn greedy_repair(bits: &mut [bool], weights: &[f64], priorities: &[f64], capacity: f64) {
+    let mut set: Vec<(usize, f64, f64)> = bits
+        .iter()
+        .zip(weights.iter().zip(priorities.iter()))
+        .enumerate()
+        .filter(|(_, (bit, _))| **bit)
+        .map(|(i, (_, (&weight, &priority)))| (i, weight, priority))
+        .collect();
+    let mut weight: f64 = set.iter().map(|(_, weight, _)| weight).sum();
+    if weight <= capacity {
+        return;
+    }
+
+    set.sort_by(|a, b| a.2.total_cmp(&b.2));
+    for (i, item_weight, _) in set {
+        if weight <= capacity {
+            break;
+        }
+        bits[i] = false;
+        weight -= item_weight;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEN: usize = 16;
+
+    fn get_parents() -> (Vec<bool>, Vec<bool>) {
+        (vec![true; LEN], vec![false; LEN])
+    }
+
+    fn assert_positions_preserved(c1: &[bool], c2: &[bool]) {
+        c1.iter().zip(c2.iter()).for_each(|(a, b)| assert_ne!(a, b));
+    }
+
+    fn count_segments(bits: &[bool]) -> usize {
+        bits.windows(2).filter(|w| w[0] != w[1]).count()
+    }
+
+    #[test]
+    fn binary_bit_flip() {
+        let mut rng = random::get_rng();
+        let (mut bits, _) = get_parents();
+
+        bit_flip(&mut bits, 0.0, &mut rng);
+        assert!(bits.iter().all(|bit| *bit));
+
+        bit_flip(&mut bits, 1.0, &mut rng);
+        assert!(bits.iter().all(|bit| !*bit));
+    }
+
+    #[test]
+    fn binary_one_point() {
+        let mut rng = random::get_rng();
+        (0..100).for_each(|_| {
+            let (mut b1, mut b2) = get_parents();
+            one_point(&mut b1, &mut b2, &mut rng);
+            assert_positions_preserved(&b1, &b2);
+            assert!(b1[0] && !b1[LEN - 1]);
+            assert_eq!(count_segments(&b1), 1);
+        });
+    }
+
+    #[test]
+    fn binary_two_point() {
+        let mut rng = random::get_rng();
+        (0..100).for_each(|_| {
+            let (mut b1, mut b2) = get_parents();
+            two_point(&mut b1, &mut b2, &mut rng);
+            assert_positions_preserved(&b1, &b2);
+            assert!(count_segments(&b1) <= 2);
+            assert!(b1.iter().any(|bit| !*bit));
+        });
+    }
+
+    #[test]
+    fn binary_uniform() {
+        let mut rng = random::get_rng();
+        let (mut b1, mut b2) = get_parents();
+        uniform(&mut b1, &mut b2, &mut rng);
+        assert_positions_preserved(&b1, &b2);
+    }
+
+    #[test]
+    fn binary_greedy_repair() {
+        let weights = vec![10.0, 20.0, 30.0, 40.0];
+        let priorities = vec![4.0, 1.0, 3.0, 2.0];
+        let mut bits = vec![true; 4];
+
+        greedy_repair(&mut bits, &weights, &priorities, 50.0);
+        assert_eq!(bits, [true, false, true, false]);
+
+        greedy_repair(&mut bits, &weights, &priorities, 50.0);
+        assert_eq!(bits, [true, false, true, false]);
+    }
+
+    #[test]
+    fn binary_greedy_repair_longer_genome() {
+        let weights = vec![10.0, 20.0, 30.0, 40.0];
+        let priorities = vec![4.0, 1.0, 3.0];
+        let mut bits = vec![true; 6];
+
+        greedy_repair(&mut bits, &weights, &priorities, 30.0);
+        assert_eq!(bits, [true, false, false, true, true, true]);
+    }
+}
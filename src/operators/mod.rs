@@ -0,0 +1,29 @@
+use crate::model::{EvaluationOperator, MutationOperator, RepairOperator};
+
+pub mod binary;
+pub mod permutation;
+pub mod real;
+
+/// Chains an evaluation onto a variation operator, for use with `evolve`, which expects the
+/// mutation operator to leave the item's values up to date.
+pub fn with_evaluation<'a>(
+    mut mutation: MutationOperator<'a>,
+    mut evaluation: EvaluationOperator<'a>,
+) -> MutationOperator<'a> {
+    Box::new(move |objectives, item| {
+        mutation(objectives, item);
+        evaluation(objectives, item);
+    })
+}
+
+/// Chains a repair onto a variation operator, so `evolve` only sees repaired items. Chain the
+/// evaluation on afterwards with `with_evaluation`.
+pub fn with_repair<'a>(
+    mut mutation: MutationOperator<'a>,
+    mut repair: RepairOperator<'a>,
+) -> MutationOperator<'a> {
+    Box::new(move |objectives, item| {
+        mutation(objectives, item);
+        repair(objectives, item);
+    })
+}
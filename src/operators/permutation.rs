@@ -0,0 +1,314 @@
+use rand::{prelude::SliceRandom, Rng};
+
+use crate::model::{CrossoverOperator, Genome, MutationOperator};
+use crate::random::{self, SeededRng};
+
+pub fn get_order_crossover_operator<'a>() -> CrossoverOperator<'a> {
+    get_crossover_operator(order_crossover)
+}
+
+pub fn get_partially_mapped_crossover_operator<'a>() -> CrossoverOperator<'a> {
+    get_crossover_operator(partially_mapped_crossover)
+}
+
+pub fn get_cycle_crossover_operator<'a>() -> CrossoverOperator<'a> {
+    get_crossover_operator(cycle_crossover)
+}
+
+pub fn get_swap_mutation_operator<'a>() -> MutationOperator<'a> {
+    get_mutation_operator(swap_mutation)
+}
+
+pub fn get_insertion_mutation_operator<'a>() -> MutationOperator<'a> {
+    get_mutation_operator(insertion_mutation)
+}
+
+pub fn get_inversion_mutation_operator<'a>() -> MutationOperator<'a> {
+    get_mutation_operator(inversion_mutation)
+}
+
+fn get_crossover_operator<'a>(
+    crossover: fn(&mut [usize], &mut [usize], &mut SeededRng),
+) -> CrossoverOperator<'a> {
+    Box::new(move |_, p1, p2| {
+        if let (Genome::Permutation(x1), Genome::Permutation(x2)) = (&mut p1.genome, &mut p2.genome)
+        {
+            if x1.len() == x2.len() && is_permutation(x1) && is_permutation(x2) {
+                crossover(x1, x2, &mut random::get_rng());
+            }
+        }
+    })
+}
+
+fn get_mutation_operator<'a>(mutation: fn(&mut [usize], &mut SeededRng)) -> MutationOperator<'a> {
+    Box::new(move |_, item| {
+        if let Genome::Permutation(x) = &mut item.genome {
+            mutation(x, &mut random::get_rng());
+        }
+    })
+}
+
+/// Whether `x` holds each of `0..x.len()` exactly once, as the crossovers require.
+fn is_permutation(x: &[usize]) -> bool {
+    let mut seen = vec![false; x.len()];
+    x.iter()
+        .all(|&gene| gene < seen.len() && !std::mem::replace(&mut seen[gene], true))
+}
+
+fn get_segment(len: usize, rng: &mut impl Rng) -> (usize, usize) {
+    let a = rng.gen_range(0..len);
+    let b = rng.gen_range(0..len);
+    (a.min(b), a.max(b) + 1)
+}
+
+/// OX: each child keeps a segment of one parent and takes the remaining cities in the order they
+/// appear in the other parent, starting after the segment. Both parents must be permutations of
+/// `0..len`.
+pub fn order_crossover(x1: &mut [usize], x2: &mut [usize], rng: &mut impl Rng) {
+    let len = x1.len();
+    if len < 2 {
+        return;
+    }
+    let (start, end) = get_segment(len, rng);
+    let c1 = get_order_child(x1, x2, start, end);
+    let c2 = get_order_child(x2, x1, start, end);
+    x1.copy_from_slice(&c1);
+    x2.copy_from_slice(&c2);
+}
+
+fn get_order_child(keep: &[usize], fill: &[usize], start: usize, end: usize) -> Vec<usize> {
+    let len = keep.len();
+    let mut child = keep.to_vec();
+    let mut used = vec![false; len];
+    keep[start..end].iter().for_each(|&gene| used[gene] = true);
+
+    let mut position = end % len;
+    for offset in 0..len {
+        let gene = fill[(end + offset) % len];
+        if !used[gene] {
+            child[position] = gene;
+            position = (position + 1) % len;
+        }
+    }
+    child
+}
+
+/// PMX: each child keeps a segment of one parent and places the other parent's genes by following
+/// the mapping the two segments define. Both parents must be permutations of `0..len`.
+pub fn partially_mapped_crossover(x1: &mut [usize], x2: &mut [usize], rng: &mut impl Rng) {
+    let len = x1.len();
+    if len < 2 {
+        return;
+    }
+    let (start, end) = get_segment(len, rng);
+    let c1 = get_partially_mapped_child(x1, x2, start, end);
+    let c2 = get_partially_mapped_child(x2, x1, start, end);
+    x1.copy_from_slice(&c1);
+    x2.copy_from_slice(&c2);
+}
+
+fn get_partially_mapped_child(
+    keep: &[usize],
+    fill: &[usize],
+    start: usize,
+    end: usize,
+) -> Vec<usize> {
+    let len = keep.len();
+    let mut position_in_fill = vec![0; len];
+    fill.iter()
+        .enumerate()
+        .for_each(|(i, &gene)| position_in_fill[gene] = i);
+
+    let mut child: Vec<Option<usize>> = vec![None; len];
+    let mut used = vec![false; len];
+    for i in start..end {
+        child[i] = Some(keep[i]);
+        used[keep[i]] = true;
+    }
+
+    for (i, &gene) in fill.iter().enumerate().take(end).skip(start) {
+        if used[gene] {
+            continue;
+        }
+        let mut position = i;
+        while (start..end).contains(&position) {
+            position = position_in_fill[keep[position]];
+        }
+        child[position] = Some(gene);
+        used[gene] = true;
+    }
+
+    child
+        .into_iter()
+        .zip(fill.iter())
+        .map(|(gene, &fill_gene)| gene.unwrap_or(fill_gene))
+        .collect()
+}
+
+/// CX: positions are split into cycles, with the children alternately inheriting whole cycles from
+/// each parent, so every gene stays at a position it held in one of the parents. Both parents must
+/// be permutations of `0..len`.
+pub fn cycle_crossover(x1: &mut [usize], x2: &mut [usize], _rng: &mut impl Rng) {
+    let len = x1.len();
+    let mut position_in_x1 = vec![0; len];
+    x1.iter()
+        .enumerate()
+        .for_each(|(i, &gene)| position_in_x1[gene] = i);
+
+    let mut visited = vec![false; len];
+    let mut swap = false;
+    for start in 0..len {
+        if visited[start] {
+            continue;
+        }
+        let mut cycle = vec![];
+        let mut position = start;
+        while !visited[position] {
+            visited[position] = true;
+            cycle.push(position);
+            position = position_in_x1[x2[position]];
+        }
+        if swap {
+            cycle
+                .into_iter()
+                .for_each(|i| std::mem::swap(&mut x1[i], &mut x2[i]));
+        }
+        swap = !swap;
+    }
+}
+
+pub fn swap_mutation(x: &mut [usize], rng: &mut impl Rng) {
+    if x.len() < 2 {
+        return;
+    }
+    let positions: Vec<usize> = (0..x.len()).collect();
+    let chosen: Vec<&usize> = positions.choose_multiple(rng, 2).collect();
+    x.swap(*chosen[0], *chosen[1]);
+}
+
+pub fn insertion_mutation(x: &mut [usize], rng: &mut impl Rng) {
+    if x.len() < 2 {
+        return;
+    }
+    let from = rng.gen_range(0..x.len());
+    let to = rng.gen_range(0..x.len());
+    if from < to {
+        x[from..=to].rotate_left(1);
+    } else {
+        x[to..=from].rotate_right(1);
+    }
+}
+
+pub fn inversion_mutation(x: &mut [usize], rng: &mut impl Rng) {
+    if x.len() < 2 {
+        return;
+    }
+    let (start, end) = get_segment(x.len(), rng);
+    x[start..end].reverse();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ModelItem;
+
+    const LEN: usize = 12;
+    const TRIALS: usize = 500;
+
+    fn get_parents(rng: &mut impl Rng) -> (Vec<usize>, Vec<usize>) {
+        let mut x1: Vec<usize> = (0..LEN).collect();
+        let mut x2: Vec<usize> = (0..LEN).collect();
+        x1.shuffle(rng);
+        x2.shuffle(rng);
+        (x1, x2)
+    }
+
+    fn assert_valid_crossover(crossover: fn(&mut [usize], &mut [usize], &mut SeededRng)) {
+        let mut rng = random::get_rng();
+        (0..TRIALS).for_each(|_| {
+            let (mut x1, mut x2) = get_parents(&mut rng);
+            crossover(&mut x1, &mut x2, &mut rng);
+            assert!(is_permutation(&x1), "{:?}", x1);
+            assert!(is_permutation(&x2), "{:?}", x2);
+        });
+    }
+
+    fn assert_valid_mutation(mutation: fn(&mut [usize], &mut SeededRng)) {
+        let mut rng = random::get_rng();
+        (0..TRIALS).for_each(|_| {
+            let (mut x, _) = get_parents(&mut rng);
+            mutation(&mut x, &mut rng);
+            assert!(is_permutation(&x), "{:?}", x);
+        });
+    }
+
+    #[test]
+    fn permutation_crossovers_are_valid() {
+        assert_valid_crossover(order_crossover);
+        assert_valid_crossover(partially_mapped_crossover);
+        assert_valid_crossover(cycle_crossover);
+    }
+
+    #[test]
+    fn permutation_mutations_are_valid() {
+        assert_valid_mutation(swap_mutation);
+        assert_valid_mutation(insertion_mutation);
+        assert_valid_mutation(inversion_mutation);
+    }
+
+    #[test]
+    fn permutation_get_order_child() {
+        let keep = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let fill = [8, 2, 6, 7, 1, 5, 4, 0, 3];
+        let child = get_order_child(&keep, &fill, 3, 6);
+        assert_eq!(child, [6, 7, 1, 3, 4, 5, 0, 8, 2]);
+    }
+
+    #[test]
+    fn permutation_get_partially_mapped_child() {
+        let keep = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let fill = [8, 2, 6, 7, 1, 5, 4, 0, 3];
+        let child = get_partially_mapped_child(&keep, &fill, 3, 6);
+        assert_eq!(child, [8, 2, 6, 3, 4, 5, 1, 0, 7]);
+    }
+
+    #[test]
+    fn permutation_cycle_crossover() {
+        let mut rng = random::get_rng();
+        let mut x1 = vec![0, 1, 2, 3, 4, 5, 6, 7];
+        let mut x2 = vec![1, 0, 3, 2, 5, 4, 7, 6];
+        cycle_crossover(&mut x1, &mut x2, &mut rng);
+        assert_eq!(x1, [0, 1, 3, 2, 4, 5, 7, 6]);
+        assert_eq!(x2, [1, 0, 2, 3, 5, 4, 6, 7]);
+    }
+
+    #[test]
+    fn permutation_swap_mutation() {
+        let mut rng = random::get_rng();
+        let original: Vec<usize> = (0..LEN).collect();
+        let mut x = original.clone();
+        swap_mutation(&mut x, &mut rng);
+        let changed = x
+            .iter()
+            .zip(original.iter())
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(changed, 2);
+    }
+
+    #[test]
+    fn permutation_crossover_operator_skips_other_labels() {
+        let mut crossover = get_order_crossover_operator();
+        let mut p1 = ModelItem::new(vec![], None);
+        let mut p2 = ModelItem::new(vec![], None);
+        p1.genome = Genome::Permutation(vec![10, 20, 30]);
+        p2.genome = Genome::Permutation(vec![30, 10, 20]);
+
+        crossover(&[], &mut p1, &mut p2);
+
+        assert_eq!(p1.genome, Genome::Permutation(vec![10, 20, 30]));
+        assert_eq!(p2.genome, Genome::Permutation(vec![30, 10, 20]));
+        assert!(!is_permutation(&[0, 0, 1]));
+        assert!(is_permutation(&[2, 0, 1]));
+    }
+}
@@ -0,0 +1,233 @@
+use rand::Rng;
+
+use crate::model::{CrossoverOperator, Genome, MutationOperator};
+use crate::random;
+
+const EPSILON: f64 = 1e-14;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Bounds {
+    pub fn new(min: f64, max: f64) -> Self {
+        Self { min, max }
+    }
+}
+
+/// Simulated binary crossover on `Genome::Real` genomes; larger `eta` keeps children closer to their parents.
+pub fn get_sbx_crossover_operator<'a>(bounds: Vec<Bounds>, eta: f64) -> CrossoverOperator<'a> {
+    Box::new(move |_, p1, p2| {
+        if let (Genome::Real(x1), Genome::Real(x2)) = (&mut p1.genome, &mut p2.genome) {
+            sbx(x1, x2, &bounds, eta, &mut random::get_rng());
+        }
+    })
+}
+
+/// Polynomial mutation on `Genome::Real` genomes, mutating each variable with `probability`.
+pub fn get_polynomial_mutation_operator<'a>(
+    bounds: Vec<Bounds>,
+    eta: f64,
+    probability: f64,
+) -> MutationOperator<'a> {
+    Box::new(move |_, item| {
+        if let Genome::Real(x) = &mut item.genome {
+            polynomial_mutation(x, &bounds, eta, probability, &mut random::get_rng());
+        }
+    })
+}
+
+/// Crosses the variables the two genomes and `bounds` have in common, leaving any others unchanged.
+pub fn sbx(x1: &mut [f64], x2: &mut [f64], bounds: &[Bounds], eta: f64, rng: &mut impl Rng) {
+    for ((x1, x2), bound) in x1.iter_mut().zip(x2.iter_mut()).zip(bounds) {
+        if !rng.gen_bool(0.5) || (*x1 - *x2).abs() <= EPSILON {
+            continue;
+        }
+        let (y1, y2) = (x1.min(*x2), x1.max(*x2));
+        let u: f64 = rng.gen();
+
+        let beta = 1.0 + 2.0 * (y1 - bound.min) / (y2 - y1);
+        let c1 = 0.5 * ((y1 + y2) - get_sbx_spread(beta, eta, u) * (y2 - y1));
+        let beta = 1.0 + 2.0 * (bound.max - y2) / (y2 - y1);
+        let c2 = 0.5 * ((y1 + y2) + get_sbx_spread(beta, eta, u) * (y2 - y1));
+
+        let (c1, c2) = (
+            c1.clamp(bound.min, bound.max),
+            c2.clamp(bound.min, bound.max),
+        );
+        if rng.gen_bool(0.5) {
+            *x1 = c2;
+            *x2 = c1;
+        } else {
+            *x1 = c1;
+            *x2 = c2;
+        }
+    }
+}
+
+fn get_sbx_spread(beta: f64, eta: f64, u: f64) -> f64 {
+    let alpha = 2.0 - beta.powf(-(eta + 1.0));
+    if u <= 1.0 / alpha {
+        (u * alpha).powf(1.0 / (eta + 1.0))
+    } else {
+        (1.0 / (2.0 - u * alpha)).powf(1.0 / (eta + 1.0))
+    }
+}
+
+/// Mutates the variables that have bounds, leaving any beyond the end of `bounds` unchanged.
+pub fn polynomial_mutation(
+    x: &mut [f64],
+    bounds: &[Bounds],
+    eta: f64,
+    probability: f64,
+    rng: &mut impl Rng,
+) {
+    for (x, bound) in x.iter_mut().zip(bounds) {
+        if !rng.gen_bool(probability) {
+            continue;
+        }
+        let range = bound.max - bound.min;
+        let y = *x;
+        let u: f64 = rng.gen();
+        let power = 1.0 / (eta + 1.0);
+
+        let delta = if u < 0.5 {
+            let xy = 1.0 - (y - bound.min) / range;
+            let value = 2.0 * u + (1.0 - 2.0 * u) * xy.powf(eta + 1.0);
+            value.powf(power) - 1.0
+        } else {
+            let xy = 1.0 - (bound.max - y) / range;
+            let value = 2.0 * (1.0 - u) + 2.0 * (u - 0.5) * xy.powf(eta + 1.0);
+            1.0 - value.powf(power)
+        };
+        *x = (y + delta * range).clamp(bound.min, bound.max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ModelItem;
+    use crate::random::SeededRng;
+
+    const TRIALS: usize = 10000;
+
+    fn get_bounds() -> Vec<Bounds> {
+        vec![Bounds::new(0.0, 1.0), Bounds::new(-5.0, 5.0)]
+    }
+
+    fn is_in_bounds(x: &[f64], bounds: &[Bounds]) -> bool {
+        x.iter()
+            .zip(bounds.iter())
+            .all(|(x, bound)| *x >= bound.min && *x <= bound.max)
+    }
+
+    fn get_sbx_mean_distance(eta: f64) -> f64 {
+        let mut rng = random::get_rng();
+        let bounds = vec![Bounds::new(0.0, 1.0)];
+        (0..TRIALS)
+            .map(|_| {
+                let (mut x1, mut x2) = (vec![0.4], vec![0.6]);
+                sbx(&mut x1, &mut x2, &bounds, eta, &mut rng);
+                (x1[0] - 0.4).abs().min((x1[0] - 0.6).abs())
+            })
+            .sum::<f64>()
+            / TRIALS as f64
+    }
+
+    #[test]
+    fn real_sbx_in_bounds() {
+        let mut rng = random::get_rng();
+        let bounds = get_bounds();
+        (0..TRIALS).for_each(|_| {
+            let mut x1 = vec![rng.gen_range(0.0..=1.0), rng.gen_range(-5.0..=5.0)];
+            let mut x2 = vec![rng.gen_range(0.0..=1.0), rng.gen_range(-5.0..=5.0)];
+            sbx(&mut x1, &mut x2, &bounds, 2.0, &mut rng);
+            assert!(is_in_bounds(&x1, &bounds));
+            assert!(is_in_bounds(&x2, &bounds));
+        });
+    }
+
+    #[test]
+    fn real_sbx_distribution() {
+        let mut rng = random::get_rng();
+        let bounds = vec![Bounds::new(0.0, 1.0)];
+        let mean = (0..TRIALS)
+            .map(|_| {
+                let (mut x1, mut x2) = (vec![0.4], vec![0.6]);
+                sbx(&mut x1, &mut x2, &bounds, 15.0, &mut rng);
+                x1[0] + x2[0]
+            })
+            .sum::<f64>()
+            / (2 * TRIALS) as f64;
+
+        assert!((mean - 0.5).abs() < 0.01);
+        assert!(get_sbx_mean_distance(20.0) < get_sbx_mean_distance(2.0));
+    }
+
+    #[test]
+    fn real_polynomial_mutation_in_bounds() {
+        let mut rng = random::get_rng();
+        let bounds = get_bounds();
+        (0..TRIALS).for_each(|_| {
+            let mut x = vec![rng.gen_range(0.0..=1.0), rng.gen_range(-5.0..=5.0)];
+            polynomial_mutation(&mut x, &bounds, 20.0, 1.0, &mut rng);
+            assert!(is_in_bounds(&x, &bounds));
+        });
+    }
+
+    #[test]
+    fn real_polynomial_mutation_distribution() {
+        let mut rng = random::get_rng();
+        let bounds = vec![Bounds::new(0.0, 1.0)];
+        let get_deltas = |eta: f64, rng: &mut SeededRng| -> Vec<f64> {
+            (0..TRIALS)
+                .map(|_| {
+                    let mut x = vec![0.5];
+                    polynomial_mutation(&mut x, &bounds, eta, 1.0, rng);
+                    x[0] - 0.5
+                })
+                .collect()
+        };
+
+        let narrow = get_deltas(20.0, &mut rng);
+        let wide = get_deltas(2.0, &mut rng);
+        let mean = narrow.iter().sum::<f64>() / TRIALS as f64;
+        let spread = |deltas: &[f64]| deltas.iter().map(|d| d.abs()).sum::<f64>();
+
+        assert!(mean.abs() < 0.005);
+        assert!(spread(&narrow) < spread(&wide));
+    }
+
+    #[test]
+    fn real_operators_skip_other_genomes() {
+        let mut crossover = get_sbx_crossover_operator(get_bounds(), 2.0);
+        let mut mutation = get_polynomial_mutation_operator(get_bounds(), 2.0, 1.0);
+        let mut p1 = ModelItem::new(vec![0.0, 0.0], None);
+        let mut p2 = ModelItem::new(vec![0.0, 0.0], None);
+        p1.genome = Genome::Real(vec![0.2, 1.0]);
+
+        crossover(&[], &mut p1, &mut p2);
+        assert_eq!(p1.genome, Genome::Real(vec![0.2, 1.0]));
+
+        mutation(&[], &mut p1);
+        assert!(matches!(&p1.genome, Genome::Real(x) if is_in_bounds(x, &get_bounds())));
+        assert_eq!(p2.genome, Genome::None);
+    }
+
+    #[test]
+    fn real_operators_mismatched_lengths() {
+        let mut rng = random::get_rng();
+        let bounds = get_bounds();
+        let (mut x1, mut x2) = (vec![0.2], vec![0.8, 1.0, 3.0]);
+        sbx(&mut x1, &mut x2, &bounds, 2.0, &mut rng);
+        polynomial_mutation(&mut x1, &bounds, 20.0, 1.0, &mut rng);
+        polynomial_mutation(&mut x2, &bounds, 20.0, 1.0, &mut rng);
+
+        assert_eq!(x1.len(), 1);
+        assert!(is_in_bounds(&x2, &bounds));
+        assert_eq!(x2[2], 3.0);
+    }
+}
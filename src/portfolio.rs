@@ -0,0 +1,370 @@
+use rand::Rng;
+
+use crate::crossover;
+use crate::model::{CrossoverOperator, Genome, Model, MutationOperator};
+use crate::mutation;
+use crate::random;
+
+/// One entry in a `Portfolio`: an optional crossover followed by a mutation.
+pub struct PortfolioOperator<'a> {
+    pub name: String,
+    pub crossover: Option<CrossoverOperator<'a>>,
+    pub mutation: MutationOperator<'a>,
+}
+
+impl<'a> PortfolioOperator<'a> {
+    pub fn new(
+        name: &str,
+        crossover: Option<CrossoverOperator<'a>>,
+        mutation: MutationOperator<'a>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            crossover,
+            mutation,
+        }
+    }
+}
+
+/// How the archive entry rates of each operator's offspring are turned into selection probabilities.
+#[derive(Debug, Clone)]
+pub enum CreditAssignment {
+    ProbabilityMatching {
+        min_probability: f64,
+        adaptation_rate: f64,
+    },
+    AdaptivePursuit {
+        min_probability: f64,
+        adaptation_rate: f64,
+        learning_rate: f64,
+    },
+}
+
+impl Default for CreditAssignment {
+    fn default() -> Self {
+        CreditAssignment::ProbabilityMatching {
+            min_probability: 0.05,
+            adaptation_rate: 0.3,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct OperatorStats {
+    pub name: String,
+    pub applications: usize,
+    pub successes: usize,
+    pub success_rate: f64,
+    pub probability: f64,
+}
+
+/// Operators chosen between per offspring pair, with credit given to those whose offspring make it
+/// into the archive. `history` holds the statistics of every generation that produced offspring.
+pub struct Portfolio<'a> {
+    pub operators: Vec<PortfolioOperator<'a>>,
+    pub credit_assignment: CreditAssignment,
+    pub probabilities: Vec<f64>,
+    pub qualities: Vec<f64>,
+    pub history: Vec<Vec<OperatorStats>>,
+    applications: Vec<usize>,
+}
+
+impl<'a> Portfolio<'a> {
+    pub fn new(operators: Vec<PortfolioOperator<'a>>, credit_assignment: CreditAssignment) -> Self {
+        let len = operators.len();
+        Self {
+            operators,
+            credit_assignment,
+            probabilities: vec![1.0 / len as f64; len],
+            qualities: vec![0.0; len],
+            history: vec![],
+            applications: vec![0; len],
+        }
+    }
+
+    fn choose(&self, rng: &mut impl Rng) -> usize {
+        let mut r = rng.gen::<f64>() * self.probabilities.iter().sum::<f64>();
+        for (i, probability) in self.probabilities.iter().enumerate() {
+            if r < *probability {
+                return i;
+            }
+            r -= probability;
+        }
+        self.probabilities.len() - 1
+    }
+
+    /// Varies the mating pool, tagging each changed item with the operator that produced it, so
+    /// unchanged copies of archive members earn no credit. Returns which items were varied.
+    pub(crate) fn apply(&mut self, model: &mut Model) -> Vec<bool> {
+        let mut rng = random::get_rng();
+        crossover::pair_neighbours(model);
+        let originals: Vec<(Genome, Vec<f32>)> = model
+            .mating_pool
+            .iter()
+            .map(|item| (item.genome.clone(), item.values.clone()))
+            .collect();
+
+        let objectives = &model.objectives;
+        let mut crossed = vec![false; model.mating_pool.len()];
+        for (pair, crossed) in model.mating_pool.chunks_mut(2).zip(crossed.chunks_mut(2)) {
+            let k = self.choose(&mut rng);
+            self.applications[k] += pair.len();
+            pair.iter_mut()
+                .for_each(|item| item.operator_index = Some(k));
+            if let ([p1, p2], Some(crossover)) = (pair, self.operators[k].crossover.as_mut()) {
+                crossover(objectives, p1, p2);
+                crossed.fill(true);
+            }
+        }
+
+        let operators = &mut self.operators;
+        let mut dispatch: MutationOperator = Box::new(|objectives, item| {
+            if let Some(k) = item.operator_index {
+                (operators[k].mutation)(objectives, item);
+            }
+        });
+        let mutated = mutation::mutate(model, &mut dispatch);
+
+        let changed: Vec<bool> = crossed
+            .into_iter()
+            .zip(mutated)
+            .map(|(crossed, mutated)| crossed || mutated)
+            .collect();
+        model
+            .mating_pool
+            .iter_mut()
+            .zip(originals)
+            .zip(changed.iter())
+            .for_each(|((item, (genome, values)), changed)| {
+                if !changed || (item.genome == genome && item.values == values) {
+                    item.operator_index = None;
+                }
+            });
+        changed
+    }
+
+    /// Credits each operator with the offspring that entered the archive during selection and
+    /// updates the selection probabilities.
+    pub(crate) fn assign_credit(&mut self, model: &mut Model) {
+        if self
+            .applications
+            .iter()
+            .all(|&applications| applications == 0)
+        {
+            return;
+        }
+        let mut successes = vec![0; self.operators.len()];
+        model.archive.iter_mut().for_each(|item| {
+            if let Some(k) = item.operator_index.take() {
+                successes[k] += 1;
+            }
+        });
+
+        let rates: Vec<f64> = successes
+            .iter()
+            .zip(self.applications.iter())
+            .map(|(&successes, &applications)| match applications {
+                0 => 0.0,
+                _ => successes as f64 / applications as f64,
+            })
+            .collect();
+        self.update_probabilities(&rates);
+
+        self.history.push(
+            self.operators
+                .iter()
+                .enumerate()
+                .map(|(k, operator)| OperatorStats {
+                    name: operator.name.clone(),
+                    applications: self.applications[k],
+                    successes: successes[k],
+                    success_rate: rates[k],
+                    probability: self.probabilities[k],
+                })
+                .collect(),
+        );
+        self.applications.fill(0);
+    }
+
+    fn update_probabilities(&mut self, rates: &[f64]) {
+        let len = self.operators.len() as f64;
+        let (min_probability, adaptation_rate) = match self.credit_assignment {
+            CreditAssignment::ProbabilityMatching {
+                min_probability,
+                adaptation_rate,
+            } => (min_probability, adaptation_rate),
+            CreditAssignment::AdaptivePursuit {
+                min_probability,
+                adaptation_rate,
+                ..
+            } => (min_probability, adaptation_rate),
+        };
+        self.qualities
+            .iter_mut()
+            .zip(rates)
+            .for_each(|(quality, rate)| *quality += adaptation_rate * (rate - *quality));
+
+        match self.credit_assignment {
+            CreditAssignment::ProbabilityMatching { .. } => {
+                let total: f64 = self.qualities.iter().sum();
+                self.probabilities
+                    .iter_mut()
+                    .zip(self.qualities.iter())
+                    .for_each(|(probability, quality)| {
+                        *probability = match total > 0.0 {
+                            true => {
+                                min_probability + (1.0 - len * min_probability) * quality / total
+                            }
+                            false => 1.0 / len,
+                        }
+                    });
+            }
+            CreditAssignment::AdaptivePursuit { learning_rate, .. } => {
+                let max_probability = 1.0 - (len - 1.0) * min_probability;
+                let best = self
+                    .qualities
+                    .iter()
+                    .enumerate()
+                    .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map(|(k, _)| k)
+                    .unwrap_or(0);
+                self.probabilities
+                    .iter_mut()
+                    .enumerate()
+                    .for_each(|(k, probability)| {
+                        let target = match k == best {
+                            true => max_probability,
+                            false => min_probability,
+                        };
+                        *probability += learning_rate * (target - *probability);
+                    });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{self, MOCK_POPULATION_COUNT};
+    use crate::model::{ModelItem, MutationSchedule};
+
+    fn get_portfolio<'a>(credit_assignment: CreditAssignment) -> Portfolio<'a> {
+        let mutation = || -> MutationOperator<'a> { Box::new(|_, _| {}) };
+        Portfolio::new(
+            vec![
+                PortfolioOperator::new("a", None, mutation()),
+                PortfolioOperator::new("b", None, mutation()),
+            ],
+            credit_assignment,
+        )
+    }
+
+    fn get_archive(successes: &[usize]) -> Vec<ModelItem> {
+        successes
+            .iter()
+            .enumerate()
+            .flat_map(|(k, &count)| {
+                (0..count).map(move |_| {
+                    let mut item = ModelItem::new(vec![0.0, 0.0], None);
+                    item.operator_index = Some(k);
+                    item
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn portfolio_apply() {
+        let mut model = mocks::get_model_with_real_genomes();
+        model.archive = model.population.clone();
+        model.mating_pool = model.population.clone();
+        model.mutation_schedule = MutationSchedule::Constant(1.0);
+        let mut portfolio = get_portfolio(CreditAssignment::default());
+        portfolio.operators.iter_mut().for_each(|operator| {
+            operator.mutation = Box::new(|_, item| {
+                if let Genome::Real(x) = &mut item.genome {
+                    x[0] += 1.0;
+                }
+            });
+        });
+
+        let changed = portfolio.apply(&mut model);
+
+        assert_eq!(changed.len(), model.mating_pool.len());
+        assert!(changed.iter().all(|&changed| changed));
+        assert!(model
+            .mating_pool
+            .iter()
+            .all(|item| item.operator_index.is_some()));
+        assert_eq!(
+            portfolio.applications.iter().sum::<usize>(),
+            model.mating_pool.len()
+        );
+    }
+
+    #[test]
+    fn portfolio_no_op_operator_earns_no_credit() {
+        let mut model = mocks::get_model_with_real_genomes();
+        model.archive = model.population.clone();
+        model.mating_pool = model.population.clone();
+        model.mutation_schedule = MutationSchedule::Constant(1.0);
+        let mut portfolio = get_portfolio(CreditAssignment::default());
+
+        portfolio.apply(&mut model);
+        model.archive = model.mating_pool.clone();
+        portfolio.assign_credit(&mut model);
+
+        let stats = &portfolio.history[0];
+        assert_eq!(
+            stats.iter().map(|stats| stats.applications).sum::<usize>(),
+            MOCK_POPULATION_COUNT
+        );
+        assert!(stats.iter().all(|stats| stats.successes == 0));
+    }
+
+    #[test]
+    fn portfolio_probability_matching() {
+        let mut model = mocks::get_model_basic();
+        let mut portfolio = get_portfolio(CreditAssignment::ProbabilityMatching {
+            min_probability: 0.1,
+            adaptation_rate: 1.0,
+        });
+        portfolio.applications = vec![10, 10];
+        model.archive = get_archive(&[6, 2]);
+
+        portfolio.assign_credit(&mut model);
+
+        assert!((portfolio.probabilities[0] - 0.7).abs() < 1e-9);
+        assert!((portfolio.probabilities[1] - 0.3).abs() < 1e-9);
+        assert!(model
+            .archive
+            .iter()
+            .all(|item| item.operator_index.is_none()));
+
+        let stats = &portfolio.history[0];
+        assert_eq!(stats[0].successes, 6);
+        assert_eq!(stats[1].success_rate, 0.2);
+        assert_eq!(portfolio.applications, [0, 0]);
+    }
+
+    #[test]
+    fn portfolio_adaptive_pursuit() {
+        let mut model = mocks::get_model_basic();
+        let mut portfolio = get_portfolio(CreditAssignment::AdaptivePursuit {
+            min_probability: 0.1,
+            adaptation_rate: 0.5,
+            learning_rate: 0.5,
+        });
+
+        (0..20).for_each(|_| {
+            portfolio.applications = vec![10, 10];
+            model.archive = get_archive(&[1, 5]);
+            portfolio.assign_credit(&mut model);
+        });
+
+        assert!((portfolio.probabilities[1] - 0.9).abs() < 1e-3);
+        assert!((portfolio.probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(portfolio.history.len(), 20);
+    }
+}
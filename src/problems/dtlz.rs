@@ -0,0 +1,331 @@
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use super::{
+    get_grid, get_linspace, get_non_dominated, get_segmented_linspace, get_simplex_points, Problem,
+};
+use crate::operators::real::Bounds;
+
+const DTLZ4_ALPHA: i32 = 100;
+/// Where `f * (1 + sin(3πf))` peaks, the right edge of the DTLZ7 front in each of the first
+/// `M - 1` objectives, and the value it peaks at.
+const DTLZ7_PEAK: (f64, f64) = (0.8594008570557163, 1.6929956344984225);
+/// The ranges of each of the first `M - 1` objectives that the DTLZ7 front covers.
+const DTLZ7_SEGMENTS: [(f64, f64); 2] = [
+    (0.0, 0.2514118360889171),
+    (0.6316265307000614, DTLZ7_PEAK.0),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DtlzVariant {
+    /// Linear front, behind 11^k - 1 local fronts.
+    Dtlz1,
+    /// Spherical front.
+    Dtlz2,
+    /// Spherical front, behind 3^k - 1 local fronts.
+    Dtlz3,
+    /// Spherical front, with solutions biased towards the edges of objective space.
+    Dtlz4,
+    /// A degenerate curve on the sphere.
+    Dtlz5,
+    /// A degenerate curve on the sphere, with a harder distance function.
+    Dtlz6,
+    /// 2^(M-1) disconnected regions.
+    Dtlz7,
+}
+
+/// The DTLZ problems for any number of objectives `M`. The last `k = variables - M + 1` variables
+/// set the distance from the front.
+#[derive(Debug, Clone)]
+pub struct Dtlz {
+    pub variant: DtlzVariant,
+    pub objectives: usize,
+    pub variables: usize,
+}
+
+impl Dtlz {
+    /// The variant with its usual `k`: 5 for DTLZ1, 20 for DTLZ7 and 10 otherwise.
+    pub fn new(variant: DtlzVariant, objectives: usize) -> Self {
+        let k = match variant {
+            DtlzVariant::Dtlz1 => 5,
+            DtlzVariant::Dtlz7 => 20,
+            _ => 10,
+        };
+        Self {
+            variant,
+            objectives,
+            variables: objectives + k - 1,
+        }
+    }
+
+    fn get_g(&self, distance: &[f64]) -> f64 {
+        match self.variant {
+            DtlzVariant::Dtlz1 | DtlzVariant::Dtlz3 => {
+                100.0
+                    * (distance.len() as f64
+                        + distance
+                            .iter()
+                            .map(|x| (x - 0.5).powi(2) - (20.0 * PI * (x - 0.5)).cos())
+                            .sum::<f64>())
+            }
+            DtlzVariant::Dtlz2 | DtlzVariant::Dtlz4 | DtlzVariant::Dtlz5 => {
+                distance.iter().map(|x| (x - 0.5).powi(2)).sum()
+            }
+            DtlzVariant::Dtlz6 => distance.iter().map(|x| x.powf(0.1)).sum(),
+            DtlzVariant::Dtlz7 => {
+                1.0 + 9.0 * distance.iter().sum::<f64>() / distance.len().max(1) as f64
+            }
+        }
+    }
+
+    /// The value of the distance variables that puts a solution on the front.
+    pub fn get_optimal_distance(&self) -> f64 {
+        match self.variant {
+            DtlzVariant::Dtlz6 | DtlzVariant::Dtlz7 => 0.0,
+            _ => 0.5,
+        }
+    }
+
+    fn get_dtlz7_last(&self, f: &[f64], g: f64) -> f64 {
+        let h = self.objectives as f64
+            - f.iter()
+                .map(|f| f / (1.0 + g) * (1.0 + (3.0 * PI * f).sin()))
+                .sum::<f64>();
+        (1.0 + g) * h
+    }
+}
+
+/// Objectives on the sphere of radius `radius`, with `angles` in `0..=π/2`.
+fn get_spherical(angles: &[f64], radius: f64) -> Vec<f64> {
+    let m = angles.len() + 1;
+    (0..m)
+        .map(|i| {
+            let cosines: f64 = angles[..m - 1 - i]
+                .iter()
+                .map(|angle| angle.cos())
+                .product();
+            match i {
+                0 => radius * cosines,
+                _ => radius * cosines * angles[m - 1 - i].sin(),
+            }
+        })
+        .collect()
+}
+
+impl Problem for Dtlz {
+    fn get_name(&self) -> String {
+        format!("{:?}", self.variant).to_uppercase()
+    }
+
+    fn get_bounds(&self) -> Vec<Bounds> {
+        vec![Bounds::new(0.0, 1.0); self.variables]
+    }
+
+    fn evaluate(&self, x: &[f64]) -> Vec<f64> {
+        let m = self.objectives;
+        let (position, distance) = x.split_at(m - 1);
+        let g = self.get_g(distance);
+        match self.variant {
+            DtlzVariant::Dtlz1 => (0..m)
+                .map(|i| {
+                    let product: f64 = position[..m - 1 - i].iter().product();
+                    match i {
+                        0 => 0.5 * (1.0 + g) * product,
+                        _ => 0.5 * (1.0 + g) * product * (1.0 - position[m - 1 - i]),
+                    }
+                })
+                .collect(),
+            DtlzVariant::Dtlz2 | DtlzVariant::Dtlz3 => {
+                let angles: Vec<f64> = position.iter().map(|x| x * FRAC_PI_2).collect();
+                get_spherical(&angles, 1.0 + g)
+            }
+            DtlzVariant::Dtlz4 => {
+                let angles: Vec<f64> = position
+                    .iter()
+                    .map(|x| x.powi(DTLZ4_ALPHA) * FRAC_PI_2)
+                    .collect();
+                get_spherical(&angles, 1.0 + g)
+            }
+            DtlzVariant::Dtlz5 | DtlzVariant::Dtlz6 => {
+                let angles: Vec<f64> = position
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| match i {
+                        0 => x * FRAC_PI_2,
+                        _ => PI / (4.0 * (1.0 + g)) * (1.0 + 2.0 * g * x),
+                    })
+                    .collect();
+                get_spherical(&angles, 1.0 + g)
+            }
+            DtlzVariant::Dtlz7 => {
+                let mut f = position.to_vec();
+                f.push(self.get_dtlz7_last(position, g));
+                f
+            }
+        }
+    }
+
+    fn get_nadir(&self) -> Vec<f32> {
+        let m = self.objectives;
+        match self.variant {
+            DtlzVariant::Dtlz1 => vec![0.5; m],
+            DtlzVariant::Dtlz2 | DtlzVariant::Dtlz3 | DtlzVariant::Dtlz4 => vec![1.0; m],
+            DtlzVariant::Dtlz5 | DtlzVariant::Dtlz6 => (0..m)
+                .map(|i| {
+                    let exponent = match i {
+                        0 => m.saturating_sub(2),
+                        _ => m - 1 - i,
+                    };
+                    FRAC_PI_4.cos().powi(exponent as i32) as f32
+                })
+                .collect(),
+            DtlzVariant::Dtlz7 => {
+                let mut nadir = vec![DTLZ7_PEAK.0 as f32; m - 1];
+                nadir.push(2.0 * m as f32);
+                nadir
+            }
+        }
+    }
+
+    fn get_ideal(&self) -> Vec<f32> {
+        let m = self.objectives;
+        let mut ideal = vec![0.0; m];
+        if self.variant == DtlzVariant::Dtlz7 {
+            ideal[m - 1] = (2.0 * m as f64 - (m - 1) as f64 * DTLZ7_PEAK.1) as f32;
+        }
+        ideal
+    }
+
+    fn get_front(&self, count: usize) -> Vec<Vec<f32>> {
+        let m = self.objectives;
+        match self.variant {
+            DtlzVariant::Dtlz1 => get_simplex_points(m, count)
+                .into_iter()
+                .map(|point| point.into_iter().map(|value| value * 0.5).collect())
+                .collect(),
+            DtlzVariant::Dtlz2 | DtlzVariant::Dtlz3 | DtlzVariant::Dtlz4 => {
+                get_simplex_points(m, count)
+                    .into_iter()
+                    .map(|point| {
+                        let norm = point.iter().map(|value| value * value).sum::<f64>().sqrt();
+                        point.into_iter().map(|value| value / norm).collect()
+                    })
+                    .collect()
+            }
+            DtlzVariant::Dtlz5 | DtlzVariant::Dtlz6 => get_linspace(0.0, FRAC_PI_2, count)
+                .into_iter()
+                .map(|angle| {
+                    let mut angles = vec![FRAC_PI_4; m - 1];
+                    angles[0] = angle;
+                    get_spherical(&angles, 1.0)
+                })
+                .collect(),
+            DtlzVariant::Dtlz7 => {
+                let resolution = (count as f64).powf(1.0 / (m - 1) as f64).ceil() as usize;
+                let values = get_segmented_linspace(&DTLZ7_SEGMENTS, resolution);
+                let points = get_grid(m - 1, &values)
+                    .into_iter()
+                    .map(|mut f| {
+                        let last = self.get_dtlz7_last(&f, 1.0);
+                        f.push(last);
+                        f
+                    })
+                    .collect();
+                get_non_dominated(points)
+            }
+        }
+        .into_iter()
+        .map(|point: Vec<f64>| point.into_iter().map(|value| value as f32).collect())
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VARIANTS: [DtlzVariant; 7] = [
+        DtlzVariant::Dtlz1,
+        DtlzVariant::Dtlz2,
+        DtlzVariant::Dtlz3,
+        DtlzVariant::Dtlz4,
+        DtlzVariant::Dtlz5,
+        DtlzVariant::Dtlz6,
+        DtlzVariant::Dtlz7,
+    ];
+
+    /// How far `values` are from the surface holding the front: the hyperplane for DTLZ1, the unit
+    /// sphere for DTLZ2–6 and `g = 1` for DTLZ7.
+    fn get_front_error(dtlz: &Dtlz, values: &[f64]) -> f64 {
+        match dtlz.variant {
+            DtlzVariant::Dtlz1 => (values.iter().sum::<f64>() - 0.5).abs(),
+            DtlzVariant::Dtlz7 => {
+                let (f, last) = values.split_at(values.len() - 1);
+                (dtlz.get_dtlz7_last(f, 1.0) - last[0]).abs()
+            }
+            _ => (values.iter().map(|value| value * value).sum::<f64>() - 1.0).abs(),
+        }
+    }
+
+    #[test]
+    fn dtlz_optimal_solutions_lie_on_front() {
+        [2, 3, 5].iter().for_each(|&objectives| {
+            VARIANTS.iter().for_each(|&variant| {
+                let dtlz = Dtlz::new(variant, objectives);
+                [0.0, 0.3, 0.7, 1.0].iter().for_each(|&position| {
+                    let mut x = vec![dtlz.get_optimal_distance(); dtlz.variables];
+                    x[..objectives - 1].fill(position);
+                    let values = dtlz.evaluate(&x);
+                    assert_eq!(values.len(), objectives);
+                    assert!(
+                        get_front_error(&dtlz, &values) < 1e-9,
+                        "{:?} {:?}",
+                        variant,
+                        values
+                    );
+                });
+            });
+        });
+    }
+
+    #[test]
+    fn dtlz_evaluate() {
+        let dtlz1 = Dtlz::new(DtlzVariant::Dtlz1, 3);
+        assert_eq!(dtlz1.variables, 7);
+        let values = dtlz1.evaluate(&[0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5]);
+        assert_eq!(values, [0.125, 0.125, 0.25]);
+
+        let dtlz2 = Dtlz::new(DtlzVariant::Dtlz2, 3);
+        let values = dtlz2.evaluate(&[0.0; 12]);
+        assert!((values[0] - 3.5).abs() < 1e-12);
+        assert!(values[1].abs() < 1e-12 && values[2].abs() < 1e-12);
+    }
+
+    #[test]
+    fn dtlz_get_front() {
+        [3, 5].iter().for_each(|&objectives| {
+            VARIANTS.iter().for_each(|&variant| {
+                let dtlz = Dtlz::new(variant, objectives);
+                let (ideal, nadir) = (dtlz.get_ideal(), dtlz.get_nadir());
+                let front = dtlz.get_front(200);
+                assert!(front.len() >= 50, "{:?} {}", variant, front.len());
+                front.iter().for_each(|point| {
+                    let values: Vec<f64> = point.iter().map(|value| *value as f64).collect();
+                    assert!(
+                        get_front_error(&dtlz, &values) < 1e-4,
+                        "{:?} {:?}",
+                        variant,
+                        point
+                    );
+                    (0..objectives).for_each(|i| {
+                        assert!(
+                            point[i] >= ideal[i] - 1e-4 && point[i] <= nadir[i] + 1e-4,
+                            "{:?} {:?}",
+                            variant,
+                            point
+                        );
+                    });
+                });
+            });
+        });
+    }
+}
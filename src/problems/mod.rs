@@ -0,0 +1,216 @@
+use crate::indicators::diversity;
+use crate::model::{
+    BoundsPolicy, Direction, EvaluationOperator, Genome, Model, ModelItem, MutationOperator,
+    Objective, Operators, Spea2Model, Variation,
+};
+use crate::operators::real::{self, Bounds};
+use crate::operators::with_evaluation;
+use crate::sampling::{self, Initialisation, Sampling};
+
+pub mod dtlz;
+pub mod wfg;
+pub mod zdt;
+
+/// A real-valued benchmark with a known Pareto front. Every objective is minimised.
+pub trait Problem {
+    fn get_name(&self) -> String;
+    fn get_bounds(&self) -> Vec<Bounds>;
+    fn evaluate(&self, x: &[f64]) -> Vec<f64>;
+
+    /// The worst value of each objective over the Pareto front.
+    fn get_nadir(&self) -> Vec<f32>;
+
+    /// The best value of each objective over the Pareto front.
+    fn get_ideal(&self) -> Vec<f32> {
+        vec![0.0; self.get_nadir().len()]
+    }
+
+    /// About `count` points spread over the Pareto front.
+    fn get_front(&self, count: usize) -> Vec<Vec<f32>>;
+}
+
+/// Runs a `Problem` as a `Spea2Model` on `Genome::Real` items, with SBX crossover and polynomial
+/// mutation for `evolve_with`.
+pub struct Benchmark<P: Problem> {
+    pub problem: P,
+    pub population_size: usize,
+    pub initialisation: Initialisation,
+    pub crossover_eta: f64,
+    pub mutation_eta: f64,
+}
+
+impl<P: Problem> Benchmark<P> {
+    pub fn new(problem: P, population_size: usize) -> Self {
+        Self {
+            problem,
+            population_size,
+            initialisation: Initialisation::default(),
+            crossover_eta: 15.0,
+            mutation_eta: 20.0,
+        }
+    }
+
+    /// Objectives scaled by the front's ideal and nadir points, so indicators compare across problems.
+    pub fn get_objectives(&self) -> Vec<Objective> {
+        self.problem
+            .get_ideal()
+            .into_iter()
+            .zip(self.problem.get_nadir())
+            .enumerate()
+            .map(|(index, (min, max))| Objective {
+                name: format!("f{}", index + 1),
+                direction: Direction::Minimised,
+                min,
+                max,
+                index,
+                bounds_policy: BoundsPolicy::Ignore,
+            })
+            .collect()
+    }
+
+    pub fn get_evaluation_operator(&self) -> EvaluationOperator<'_> {
+        Box::new(|_, item| evaluate(&self.problem, item))
+    }
+
+    pub fn get_operators(&self) -> Operators<'_> {
+        let bounds = self.problem.get_bounds();
+        let probability = 1.0 / bounds.len().max(1) as f64;
+        Operators {
+            variation: Variation::Genetic,
+            crossover: Some(real::get_sbx_crossover_operator(
+                bounds.clone(),
+                self.crossover_eta,
+            )),
+            mutation: real::get_polynomial_mutation_operator(
+                bounds,
+                self.mutation_eta,
+                probability,
+            ),
+            portfolio: None,
+            repair: None,
+            evaluation: Some(self.get_evaluation_operator()),
+        }
+    }
+}
+
+fn evaluate<P: Problem>(problem: &P, item: &mut ModelItem) {
+    if let Genome::Real(x) = &item.genome {
+        item.values = problem
+            .evaluate(x)
+            .into_iter()
+            .map(|value| value as f32)
+            .collect();
+    }
+}
+
+impl<P: Problem> Spea2Model for Benchmark<P> {
+    fn get_model(&self) -> Model {
+        let objectives = self.get_objectives();
+        let population = self.initialisation.get_population(
+            &objectives,
+            &self.problem.get_bounds(),
+            self.population_size,
+            &mut self.get_evaluation_operator(),
+        );
+        Model::new(objectives, population)
+    }
+
+    fn get_mutation_operator(&mut self) -> MutationOperator<'_> {
+        let bounds = self.problem.get_bounds();
+        let probability = 1.0 / bounds.len().max(1) as f64;
+        let mutation =
+            real::get_polynomial_mutation_operator(bounds, self.mutation_eta, probability);
+        with_evaluation(mutation, self.get_evaluation_operator())
+    }
+
+    fn evaluate(&mut self, _: &[Objective], item: &mut ModelItem) {
+        evaluate(&self.problem, item);
+    }
+
+    /// A uniformly random item, as the deterministic samplers would give the same point every time.
+    fn get_random_item(&mut self, _: &[Objective]) -> Option<ModelItem> {
+        let bounds = self.problem.get_bounds();
+        let x = sampling::sample(&Sampling::Uniform, &bounds, 1).pop()?;
+        let mut item = ModelItem::new(vec![], None);
+        item.genome = Genome::Real(x);
+        evaluate(&self.problem, &mut item);
+        Some(item)
+    }
+}
+
+/// `count` evenly spaced values from `start` to `end` inclusive.
+fn get_linspace(start: f64, end: f64, count: usize) -> Vec<f64> {
+    match count {
+        0 => vec![],
+        1 => vec![start],
+        _ => (0..count)
+            .map(|i| start + (end - start) * i as f64 / (count - 1) as f64)
+            .collect(),
+    }
+}
+
+/// `count` values spread over `segments` in proportion to their lengths, each segment keeping its
+/// end points.
+fn get_segmented_linspace(segments: &[(f64, f64)], count: usize) -> Vec<f64> {
+    let total: f64 = segments.iter().map(|(start, end)| end - start).sum();
+    segments
+        .iter()
+        .flat_map(|&(start, end)| {
+            let share = (count as f64 * (end - start) / total).round() as usize;
+            get_linspace(start, end, share.max(2))
+        })
+        .collect()
+}
+
+/// Every point of the grid taking `values` in each dimension.
+fn get_grid(dimensions: usize, values: &[f64]) -> Vec<Vec<f64>> {
+    (0..dimensions).fold(vec![vec![]], |grid, _| {
+        grid.into_iter()
+            .flat_map(|prefix: Vec<f64>| {
+                values.iter().map(move |&value| {
+                    let mut point = prefix.clone();
+                    point.push(value);
+                    point
+                })
+            })
+            .collect()
+    })
+}
+
+/// At least `count` evenly spread points on the unit simplex, where the coordinates sum to 1.
+fn get_simplex_points(dimensions: usize, count: usize) -> Vec<Vec<f64>> {
+    let get_size = |divisions: usize| (1..dimensions).fold(1, |size, i| size * (divisions + i) / i);
+    let divisions = (1..)
+        .find(|&divisions| get_size(divisions) >= count)
+        .unwrap();
+    diversity::get_weight_vectors(dimensions, divisions)
+}
+
+/// Drops points dominated by another, all objectives being minimised.
+fn get_non_dominated(points: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    let dominates = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(a, b)| a <= b) && a != b;
+    points
+        .iter()
+        .filter(|point| !points.iter().any(|other| dominates(other, point)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::zdt::{Zdt, ZdtVariant};
+    use super::*;
+
+    #[test]
+    fn problems_get_random_item() {
+        let mut benchmark = Benchmark::new(Zdt::new(ZdtVariant::Zdt1), 10);
+        benchmark.initialisation = Initialisation::new(Sampling::Sobol);
+        let objectives = benchmark.get_objectives();
+
+        let a = benchmark.get_random_item(&objectives).unwrap();
+        let b = benchmark.get_random_item(&objectives).unwrap();
+
+        assert_ne!(a.genome, b.genome);
+        assert_eq!(a.values.len(), 2);
+    }
+}
@@ -0,0 +1,580 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::ops::Range;
+
+use super::{get_grid, get_linspace, get_non_dominated, get_simplex_points, Problem};
+use crate::operators::real::Bounds;
+
+const EPSILON: f64 = 1e-10;
+/// `b_param` settings shared by WFG7–9.
+const PARAMETER_DEPENDENT_BIAS: (f64, f64, f64) = (0.98 / 49.98, 0.02, 50.0);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WfgVariant {
+    /// Convex and mixed front, with a flat region and polynomial bias.
+    Wfg1,
+    /// Convex, disconnected front with non-separable distance parameters.
+    Wfg2,
+    /// Linear, degenerate front with non-separable distance parameters.
+    Wfg3,
+    /// Concave front, multi-modal.
+    Wfg4,
+    /// Concave front, deceptive.
+    Wfg5,
+    /// Concave front, non-separable.
+    Wfg6,
+    /// Concave front, with the position parameters biased by the distance parameters.
+    Wfg7,
+    /// Concave front, with the distance parameters biased by the position parameters.
+    Wfg8,
+    /// Concave front, biased, deceptive, multi-modal and non-separable.
+    Wfg9,
+}
+
+/// The WFG problems for `M` objectives. The first `position` variables place a solution along the
+/// front and the remaining `distance` variables set how far it is from the front. `position` must
+/// be a multiple of `M - 1`, and `distance` must be even for WFG2 and WFG3. Variable `i` lies in
+/// `0..=2(i + 1)`.
+#[derive(Debug, Clone)]
+pub struct Wfg {
+    pub variant: WfgVariant,
+    pub objectives: usize,
+    pub position: usize,
+    pub distance: usize,
+}
+
+impl Wfg {
+    /// The variant with `2(M - 1)` position and 20 distance variables.
+    pub fn new(variant: WfgVariant, objectives: usize) -> Self {
+        Self {
+            variant,
+            objectives,
+            position: 2 * (objectives - 1),
+            distance: 20,
+        }
+    }
+
+    /// A Pareto-optimal solution placed on the front by `position`, with values in `0..=1`.
+    pub fn get_optimal_solution(&self, position: &[f64]) -> Vec<f64> {
+        let (k, n) = (self.position, self.position + self.distance);
+        let (a, b, c) = PARAMETER_DEPENDENT_BIAS;
+        let get_optimal = |u: f64| {
+            let exponent = b + (c - b) * (a - (1.0 - 2.0 * u) * ((0.5 - u).floor() + a).abs());
+            0.35f64.powf(1.0 / exponent)
+        };
+
+        let mut y = position[..k].to_vec();
+        y.resize(n, 0.35);
+        match self.variant {
+            WfgVariant::Wfg8 => {
+                (k..n).for_each(|i| y[i] = get_optimal(reduction_sum(&y[..i], &vec![1.0; i])));
+            }
+            WfgVariant::Wfg9 => (k..n - 1).rev().for_each(|i| {
+                y[i] = get_optimal(reduction_sum(&y[i + 1..], &vec![1.0; n - i - 1]));
+            }),
+            _ => {}
+        }
+        y.iter()
+            .enumerate()
+            .map(|(i, y)| y * 2.0 * (i + 1) as f64)
+            .collect()
+    }
+
+    /// The `M` transformed parameters `t`, the last being the distance from the front.
+    fn get_transformed(&self, y: &[f64]) -> Vec<f64> {
+        let k = self.position;
+        let n = y.len();
+        let map = |y: &[f64], transform: &dyn Fn(usize, f64) -> f64| -> Vec<f64> {
+            y.iter()
+                .enumerate()
+                .map(|(i, &y)| transform(i, y))
+                .collect()
+        };
+        let shift_distance = |i: usize, y: f64| match i < k {
+            true => y,
+            false => shift_linear(y, 0.35),
+        };
+        let (a, b, c) = PARAMETER_DEPENDENT_BIAS;
+
+        match self.variant {
+            WfgVariant::Wfg1 => {
+                let y = map(y, &shift_distance);
+                let y = map(&y, &|i, y| match i < k {
+                    true => y,
+                    false => bias_flat(y, 0.8, 0.75, 0.85),
+                });
+                let y = map(&y, &|_, y| bias_polynomial(y, 0.02));
+                let weights: Vec<f64> = (0..n).map(|i| 2.0 * (i + 1) as f64).collect();
+                self.reduce_sum(&y, &weights)
+            }
+            WfgVariant::Wfg2 | WfgVariant::Wfg3 => {
+                let y = map(y, &shift_distance);
+                let mut reduced = y[..k].to_vec();
+                reduced.extend(
+                    y[k..]
+                        .chunks(2)
+                        .map(|pair| reduction_non_separable(pair, 2)),
+                );
+                self.reduce_sum(&reduced, &vec![1.0; reduced.len()])
+            }
+            WfgVariant::Wfg4 => {
+                let y = map(y, &|_, y| shift_multi_modal(y, 30.0, 10.0, 0.35));
+                self.reduce_sum(&y, &vec![1.0; n])
+            }
+            WfgVariant::Wfg5 => {
+                let y = map(y, &|_, y| shift_deceptive(y, 0.35, 0.001, 0.05));
+                self.reduce_sum(&y, &vec![1.0; n])
+            }
+            WfgVariant::Wfg6 => {
+                let y = map(y, &shift_distance);
+                self.reduce_non_separable(&y)
+            }
+            WfgVariant::Wfg7 => {
+                let biased = map(y, &|i, value| match i < k {
+                    true => bias_parameter(
+                        value,
+                        reduction_sum(&y[i + 1..], &vec![1.0; n - i - 1]),
+                        a,
+                        b,
+                        c,
+                    ),
+                    false => value,
+                });
+                let y = map(&biased, &shift_distance);
+                self.reduce_sum(&y, &vec![1.0; n])
+            }
+            WfgVariant::Wfg8 => {
+                let biased = map(y, &|i, value| match i < k {
+                    true => value,
+                    false => bias_parameter(value, reduction_sum(&y[..i], &vec![1.0; i]), a, b, c),
+                });
+                let y = map(&biased, &shift_distance);
+                self.reduce_sum(&y, &vec![1.0; n])
+            }
+            WfgVariant::Wfg9 => {
+                let biased = map(y, &|i, value| match i < n - 1 {
+                    true => bias_parameter(
+                        value,
+                        reduction_sum(&y[i + 1..], &vec![1.0; n - i - 1]),
+                        a,
+                        b,
+                        c,
+                    ),
+                    false => value,
+                });
+                let y = map(&biased, &|i, y| match i < k {
+                    true => shift_deceptive(y, 0.35, 0.001, 0.05),
+                    false => shift_multi_modal(y, 30.0, 95.0, 0.35),
+                });
+                self.reduce_non_separable(&y)
+            }
+        }
+    }
+
+    /// The `M - 1` groups of position parameters followed by the distance parameters.
+    fn get_groups(&self, len: usize) -> Vec<Range<usize>> {
+        let size = self.position / (self.objectives - 1).max(1);
+        let mut groups: Vec<Range<usize>> = (0..self.objectives - 1)
+            .map(|i| i * size..(i + 1) * size)
+            .collect();
+        groups.push(self.position..len);
+        groups
+    }
+
+    fn reduce_sum(&self, y: &[f64], weights: &[f64]) -> Vec<f64> {
+        self.get_groups(y.len())
+            .into_iter()
+            .map(|group| reduction_sum(&y[group.clone()], &weights[group]))
+            .collect()
+    }
+
+    fn reduce_non_separable(&self, y: &[f64]) -> Vec<f64> {
+        self.get_groups(y.len())
+            .into_iter()
+            .map(|group| reduction_non_separable(&y[group.clone()], group.len()))
+            .collect()
+    }
+
+    /// The shape function values `h` for position parameters `x`.
+    fn get_shape(&self, x: &[f64]) -> Vec<f64> {
+        let m = self.objectives;
+        (0..m)
+            .map(|i| match (self.variant, i == m - 1) {
+                (WfgVariant::Wfg1, true) => mixed(x, 1.0, 5.0),
+                (WfgVariant::Wfg1, false) => convex(x, i),
+                (WfgVariant::Wfg2, true) => disconnected(x, 1.0, 1.0, 5.0),
+                (WfgVariant::Wfg2, false) => convex(x, i),
+                (WfgVariant::Wfg3, _) => linear(x, i),
+                _ => concave(x, i),
+            })
+            .collect()
+    }
+
+    /// The degeneracy constants `A`, zero for the position parameters that WFG3 collapses.
+    fn get_degeneracy(&self) -> Vec<f64> {
+        (0..self.objectives - 1)
+            .map(|i| match (self.variant, i) {
+                (WfgVariant::Wfg3, i) if i > 0 => 0.0,
+                _ => 1.0,
+            })
+            .collect()
+    }
+
+    /// The point of the front at position parameters `x`.
+    fn get_front_point(&self, x: &[f64]) -> Vec<f64> {
+        get_objectives(0.0, &self.get_shape(x))
+    }
+}
+
+impl Problem for Wfg {
+    fn get_name(&self) -> String {
+        format!("{:?}", self.variant).to_uppercase()
+    }
+
+    fn get_bounds(&self) -> Vec<Bounds> {
+        (0..self.position + self.distance)
+            .map(|i| Bounds::new(0.0, 2.0 * (i + 1) as f64))
+            .collect()
+    }
+
+    fn evaluate(&self, z: &[f64]) -> Vec<f64> {
+        let y: Vec<f64> = z
+            .iter()
+            .enumerate()
+            .map(|(i, z)| correct_to_unit(z / (2.0 * (i + 1) as f64)))
+            .collect();
+        let t = self.get_transformed(&y);
+        let x = get_shape_parameters(&t, &self.get_degeneracy());
+        get_objectives(t[t.len() - 1], &self.get_shape(&x))
+    }
+
+    fn get_nadir(&self) -> Vec<f32> {
+        match self.variant {
+            WfgVariant::Wfg3 => {
+                let get_end = |x1: f64| {
+                    let mut x = vec![0.5; self.objectives - 1];
+                    x[0] = x1;
+                    self.get_front_point(&x)
+                };
+                get_end(0.0)
+                    .into_iter()
+                    .zip(get_end(1.0))
+                    .map(|(a, b)| a.max(b) as f32)
+                    .collect()
+            }
+            _ => (0..self.objectives).map(|i| 2.0 * (i + 1) as f32).collect(),
+        }
+    }
+
+    fn get_front(&self, count: usize) -> Vec<Vec<f32>> {
+        let m = self.objectives;
+        match self.variant {
+            WfgVariant::Wfg1 | WfgVariant::Wfg2 => {
+                let resolution = (count as f64).powf(1.0 / (m - 1) as f64).ceil() as usize;
+                let values = get_linspace(0.0, 1.0, resolution.max(2));
+                let points = get_grid(m - 1, &values)
+                    .into_iter()
+                    .map(|x| self.get_front_point(&x))
+                    .collect();
+                get_non_dominated(points)
+            }
+            WfgVariant::Wfg3 => get_linspace(0.0, 1.0, count)
+                .into_iter()
+                .map(|x1| {
+                    let mut x = vec![0.5; m - 1];
+                    x[0] = x1;
+                    self.get_front_point(&x)
+                })
+                .collect(),
+            _ => get_simplex_points(m, count)
+                .into_iter()
+                .map(|point| {
+                    let norm = point.iter().map(|value| value * value).sum::<f64>().sqrt();
+                    point
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, value)| 2.0 * (i + 1) as f64 * value / norm)
+                        .collect()
+                })
+                .collect(),
+        }
+        .into_iter()
+        .map(|point: Vec<f64>| point.into_iter().map(|value| value as f32).collect())
+        .collect()
+    }
+}
+
+/// Clamps values pushed just outside `0..=1` by rounding.
+fn correct_to_unit(value: f64) -> f64 {
+    value.clamp(0.0, 1.0)
+}
+
+/// The position parameters `x` from the transformed parameters `t`, using degeneracy constants `a`.
+pub fn get_shape_parameters(t: &[f64], a: &[f64]) -> Vec<f64> {
+    let distance = t[t.len() - 1];
+    t[..t.len() - 1]
+        .iter()
+        .zip(a)
+        .map(|(t, a)| distance.max(*a) * (t - 0.5) + 0.5)
+        .collect()
+}
+
+/// `f_m = D x_M + S_m h_m`, with `D = 1` and `S_m = 2m`.
+pub fn get_objectives(distance: f64, shape: &[f64]) -> Vec<f64> {
+    shape
+        .iter()
+        .enumerate()
+        .map(|(i, h)| distance + 2.0 * (i + 1) as f64 * h)
+        .collect()
+}
+
+pub fn bias_polynomial(y: f64, alpha: f64) -> f64 {
+    correct_to_unit(y.powf(alpha))
+}
+
+/// Maps `b..=c` to the flat value `a`.
+pub fn bias_flat(y: f64, a: f64, b: f64, c: f64) -> f64 {
+    let value = a + (y - b).floor().min(0.0) * a * (b - y) / b
+        - (c - y).floor().min(0.0) * (1.0 - a) * (y - c) / (1.0 - c);
+    correct_to_unit(value)
+}
+
+/// Biases `y` by the exponent `b..=c` chosen by `u`, another parameter or reduction of several.
+pub fn bias_parameter(y: f64, u: f64, a: f64, b: f64, c: f64) -> f64 {
+    let exponent = b + (c - b) * (a - (1.0 - 2.0 * u) * ((0.5 - u).floor() + a).abs());
+    correct_to_unit(y.powf(exponent))
+}
+
+/// Moves the optimum from `a` to 0. Values within rounding error of `a` map to exactly 0, which
+/// the steep polynomial bias of WFG1 would otherwise blow up.
+pub fn shift_linear(y: f64, a: f64) -> f64 {
+    if (y - a).abs() < EPSILON {
+        return 0.0;
+    }
+    correct_to_unit((y - a).abs() / ((a - y).floor() + a).abs())
+}
+
+/// A global optimum at `a`, within a window of width `2b`, and deceptive optima at 0 and 1 of
+/// value `c`.
+pub fn shift_deceptive(y: f64, a: f64, b: f64, c: f64) -> f64 {
+    let value = 1.0
+        + ((y - a).abs() - b)
+            * ((y - a + b).floor() * (1.0 - c + (a - b) / b) / (a - b)
+                + (a + b - y).floor() * (1.0 - c + (1.0 - a - b) / b) / (1.0 - a - b)
+                + 1.0 / b);
+    correct_to_unit(value)
+}
+
+/// A global optimum at `c` among `a` local optima per side, with `b` setting their size.
+pub fn shift_multi_modal(y: f64, a: f64, b: f64, c: f64) -> f64 {
+    let offset = (y - c).abs() / (2.0 * ((c - y).floor() + c));
+    let value = (1.0 + ((4.0 * a + 2.0) * PI * (0.5 - offset)).cos() + 4.0 * b * offset * offset)
+        / (b + 2.0);
+    correct_to_unit(value)
+}
+
+pub fn reduction_sum(y: &[f64], weights: &[f64]) -> f64 {
+    let total: f64 = weights.iter().sum();
+    correct_to_unit(y.iter().zip(weights).map(|(y, w)| y * w).sum::<f64>() / total)
+}
+
+/// A reduction in which each parameter interacts with the `a - 1` that follow it.
+pub fn reduction_non_separable(y: &[f64], a: usize) -> f64 {
+    let len = y.len();
+    let numerator: f64 = (0..len)
+        .map(|j| {
+            y[j] + (0..a.saturating_sub(1))
+                .map(|k| (y[j] - y[(j + k + 1) % len]).abs())
+                .sum::<f64>()
+        })
+        .sum();
+    let half = a.div_ceil(2) as f64;
+    let denominator = len as f64 / a as f64 * half * (1.0 + 2.0 * a as f64 - 2.0 * half);
+    correct_to_unit(numerator / denominator)
+}
+
+/// Shape value of objective `m`, counted from 0, for the `M - 1` position parameters `x`.
+pub fn linear(x: &[f64], m: usize) -> f64 {
+    let len = x.len();
+    let product: f64 = x[..len - m].iter().product();
+    match m {
+        0 => product,
+        _ => product * (1.0 - x[len - m]),
+    }
+}
+
+pub fn convex(x: &[f64], m: usize) -> f64 {
+    let len = x.len();
+    let product: f64 = x[..len - m]
+        .iter()
+        .map(|x| 1.0 - (x * FRAC_PI_2).cos())
+        .product();
+    match m {
+        0 => product,
+        _ => product * (1.0 - (x[len - m] * FRAC_PI_2).sin()),
+    }
+}
+
+pub fn concave(x: &[f64], m: usize) -> f64 {
+    let len = x.len();
+    let product: f64 = x[..len - m].iter().map(|x| (x * FRAC_PI_2).sin()).product();
+    match m {
+        0 => product,
+        _ => product * (x[len - m] * FRAC_PI_2).cos(),
+    }
+}
+
+/// For the last objective: `a` alternating convex and concave sections.
+pub fn mixed(x: &[f64], alpha: f64, a: f64) -> f64 {
+    let value = 1.0 - x[0] - (2.0 * a * PI * x[0] + FRAC_PI_2).cos() / (2.0 * a * PI);
+    value.powf(alpha)
+}
+
+/// For the last objective: `a` disconnected regions.
+pub fn disconnected(x: &[f64], alpha: f64, beta: f64, a: f64) -> f64 {
+    1.0 - x[0].powf(alpha) * (a * x[0].powf(beta) * PI).cos().powi(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::evolve_with;
+    use crate::indicators::distance;
+    use crate::model::Spea2Model;
+    use crate::problems::Benchmark;
+    use crate::random;
+
+    const VARIANTS: [WfgVariant; 9] = [
+        WfgVariant::Wfg1,
+        WfgVariant::Wfg2,
+        WfgVariant::Wfg3,
+        WfgVariant::Wfg4,
+        WfgVariant::Wfg5,
+        WfgVariant::Wfg6,
+        WfgVariant::Wfg7,
+        WfgVariant::Wfg8,
+        WfgVariant::Wfg9,
+    ];
+
+    #[test]
+    fn wfg_transformations() {
+        assert_eq!(shift_linear(0.35, 0.35), 0.0);
+        assert_eq!(shift_linear(1.0, 0.35), 1.0);
+        assert_eq!(shift_multi_modal(0.35, 30.0, 10.0, 0.35), 0.0);
+        assert!(shift_deceptive(0.35, 0.35, 0.001, 0.05).abs() < 1e-12);
+        assert_eq!(bias_polynomial(0.5, 2.0), 0.25);
+        assert_eq!(bias_flat(0.8, 0.8, 0.75, 0.85), 0.8);
+        assert!((bias_flat(0.0, 0.8, 0.75, 0.85)).abs() < 1e-12);
+        assert!((reduction_sum(&[0.2, 0.4], &[1.0, 3.0]) - 0.35).abs() < 1e-12);
+        assert_eq!(reduction_non_separable(&[1.0, 0.0], 2), 1.0);
+        assert_eq!(reduction_non_separable(&[0.0, 0.0, 0.0], 3), 0.0);
+    }
+
+    #[test]
+    fn wfg_shapes() {
+        let x = [0.3, 0.8];
+        let linear_sum: f64 = (0..3).map(|m| linear(&x, m)).sum();
+        assert!((linear_sum - 1.0).abs() < 1e-12);
+        let concave_norm: f64 = (0..3).map(|m| concave(&x, m).powi(2)).sum();
+        assert!((concave_norm - 1.0).abs() < 1e-12);
+        assert_eq!(convex(&[0.0, 0.0], 0), 0.0);
+        assert!((convex(&[1.0, 1.0], 0) - 1.0).abs() < 1e-12);
+        assert!((mixed(&[0.0], 1.0, 5.0) - 1.0).abs() < 1e-12);
+        assert!(disconnected(&[1.0], 1.0, 1.0, 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn wfg_optimal_solutions_lie_on_front() {
+        random::seed(0);
+        let mut rng = random::get_rng();
+        [2, 3, 5].iter().for_each(|&objectives| {
+            VARIANTS.iter().for_each(|&variant| {
+                let wfg = Wfg::new(variant, objectives);
+                let bounds = wfg.get_bounds();
+                (0..10).for_each(|_| {
+                    let position: Vec<f64> = (0..wfg.position).map(|_| rng.gen()).collect();
+                    let z = wfg.get_optimal_solution(&position);
+                    assert!(z
+                        .iter()
+                        .zip(&bounds)
+                        .all(|(z, b)| *z >= b.min && *z <= b.max));
+
+                    let y: Vec<f64> = z
+                        .iter()
+                        .enumerate()
+                        .map(|(i, z)| z / (2.0 * (i + 1) as f64))
+                        .collect();
+                    let t = wfg.get_transformed(&y);
+                    assert!(t[objectives - 1] < 1e-9, "{:?} {:?}", variant, t);
+
+                    let values = wfg.evaluate(&z);
+                    if !matches!(
+                        variant,
+                        WfgVariant::Wfg1 | WfgVariant::Wfg2 | WfgVariant::Wfg3
+                    ) {
+                        let norm: f64 = values
+                            .iter()
+                            .enumerate()
+                            .map(|(i, value)| (value / (2.0 * (i + 1) as f64)).powi(2))
+                            .sum();
+                        assert!((norm - 1.0).abs() < 1e-6, "{:?} {:?}", variant, values);
+                    }
+                });
+            });
+        });
+    }
+
+    #[test]
+    fn wfg_get_front() {
+        [2, 3].iter().for_each(|&objectives| {
+            VARIANTS.iter().for_each(|&variant| {
+                let wfg = Wfg::new(variant, objectives);
+                let nadir = wfg.get_nadir();
+                let front = wfg.get_front(100);
+                assert!(front.len() >= 10, "{:?} {}", variant, front.len());
+                front.iter().for_each(|point| {
+                    assert!(point
+                        .iter()
+                        .zip(&nadir)
+                        .all(|(value, nadir)| *value >= 0.0 && *value <= nadir + 1e-4));
+                });
+            });
+        });
+    }
+
+    #[test]
+    fn wfg_custom_problem() {
+        let t = [0.25, 0.0];
+        let x = get_shape_parameters(&t, &[1.0]);
+        assert_eq!(x, [0.25]);
+        let values = get_objectives(t[1], &[convex(&x, 0), convex(&x, 1)]);
+        assert!((values[0] - 2.0 * (1.0 - (0.25 * FRAC_PI_2).cos())).abs() < 1e-12);
+        assert!((values[1] - 4.0 * (1.0 - (0.25 * FRAC_PI_2).sin())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn wfg_convergence() {
+        [WfgVariant::Wfg1, WfgVariant::Wfg4]
+            .iter()
+            .for_each(|&variant| {
+                random::seed(1);
+                let benchmark = Benchmark::new(Wfg::new(variant, 2), 40);
+                let front = benchmark.problem.get_front(200);
+                let mut model = benchmark.get_model();
+                let mut operators = benchmark.get_operators();
+
+                evolve_with(&mut model, &mut operators).unwrap();
+                let objectives = model.objectives.clone();
+                let before =
+                    distance::inverted_generational_distance(&objectives, &model.archive, &front);
+                for _ in 0..50 {
+                    evolve_with(&mut model, &mut operators).unwrap();
+                }
+                let after =
+                    distance::inverted_generational_distance(&objectives, &model.archive, &front);
+
+                assert!(after < before, "{:?} {} - {}", variant, before, after);
+            });
+    }
+}
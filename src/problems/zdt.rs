@@ -0,0 +1,226 @@
+use std::f64::consts::PI;
+
+use super::{get_linspace, get_segmented_linspace, Problem};
+use crate::operators::real::Bounds;
+
+/// The disconnected pieces of the ZDT3 front, as ranges of `f1`.
+const ZDT3_SEGMENTS: [(f64, f64); 5] = [
+    (0.0, 0.0830015349),
+    (0.1822287280, 0.2577623634),
+    (0.4093136748, 0.4538821041),
+    (0.6183967944, 0.6525117038),
+    (0.8233317983, 0.8518328654),
+];
+/// The smallest `f1` ZDT6 can reach.
+const ZDT6_MIN_F1: f64 = 0.2807753191;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZdtVariant {
+    /// Convex front.
+    Zdt1,
+    /// Non-convex front.
+    Zdt2,
+    /// Front split into five disconnected pieces.
+    Zdt3,
+    /// Convex front behind 21^9 local fronts.
+    Zdt4,
+    /// Non-convex front, with solutions sparse near it and unevenly spread along it.
+    Zdt6,
+}
+
+/// The bi-objective ZDT problems. The Pareto-optimal set is `x[1..] == 0`.
+#[derive(Debug, Clone)]
+pub struct Zdt {
+    pub variant: ZdtVariant,
+    pub variables: usize,
+}
+
+impl Zdt {
+    /// The variant with its usual number of variables: 30 for ZDT1–3 and 10 for ZDT4 and ZDT6.
+    pub fn new(variant: ZdtVariant) -> Self {
+        let variables = match variant {
+            ZdtVariant::Zdt1 | ZdtVariant::Zdt2 | ZdtVariant::Zdt3 => 30,
+            ZdtVariant::Zdt4 | ZdtVariant::Zdt6 => 10,
+        };
+        Self { variant, variables }
+    }
+
+    fn get_g(&self, x: &[f64]) -> f64 {
+        let tail = &x[1..];
+        let scale = tail.len().max(1) as f64;
+        match self.variant {
+            ZdtVariant::Zdt1 | ZdtVariant::Zdt2 | ZdtVariant::Zdt3 => {
+                1.0 + 9.0 * tail.iter().sum::<f64>() / scale
+            }
+            ZdtVariant::Zdt4 => {
+                1.0 + 10.0 * tail.len() as f64
+                    + tail
+                        .iter()
+                        .map(|x| x * x - 10.0 * (4.0 * PI * x).cos())
+                        .sum::<f64>()
+            }
+            ZdtVariant::Zdt6 => 1.0 + 9.0 * (tail.iter().sum::<f64>() / scale).powf(0.25),
+        }
+    }
+
+    /// `f2` on the front, where `g` is 1.
+    fn get_front_f2(&self, f1: f64) -> f64 {
+        match self.variant {
+            ZdtVariant::Zdt1 | ZdtVariant::Zdt4 => 1.0 - f1.sqrt(),
+            ZdtVariant::Zdt2 | ZdtVariant::Zdt6 => 1.0 - f1 * f1,
+            ZdtVariant::Zdt3 => 1.0 - f1.sqrt() - f1 * (10.0 * PI * f1).sin(),
+        }
+    }
+}
+
+impl Problem for Zdt {
+    fn get_name(&self) -> String {
+        format!("{:?}", self.variant).to_uppercase()
+    }
+
+    fn get_bounds(&self) -> Vec<Bounds> {
+        (0..self.variables)
+            .map(|i| match (self.variant, i) {
+                (ZdtVariant::Zdt4, i) if i > 0 => Bounds::new(-5.0, 5.0),
+                _ => Bounds::new(0.0, 1.0),
+            })
+            .collect()
+    }
+
+    fn evaluate(&self, x: &[f64]) -> Vec<f64> {
+        let f1 = match self.variant {
+            ZdtVariant::Zdt6 => 1.0 - (-4.0 * x[0]).exp() * (6.0 * PI * x[0]).sin().powi(6),
+            _ => x[0],
+        };
+        let g = self.get_g(x);
+        let h = match self.variant {
+            ZdtVariant::Zdt1 | ZdtVariant::Zdt4 => 1.0 - (f1 / g).sqrt(),
+            ZdtVariant::Zdt2 | ZdtVariant::Zdt6 => 1.0 - (f1 / g).powi(2),
+            ZdtVariant::Zdt3 => 1.0 - (f1 / g).sqrt() - f1 / g * (10.0 * PI * f1).sin(),
+        };
+        vec![f1, g * h]
+    }
+
+    fn get_nadir(&self) -> Vec<f32> {
+        match self.variant {
+            ZdtVariant::Zdt3 => vec![ZDT3_SEGMENTS[4].1 as f32, 1.0],
+            ZdtVariant::Zdt6 => vec![1.0, self.get_front_f2(ZDT6_MIN_F1) as f32],
+            _ => vec![1.0, 1.0],
+        }
+    }
+
+    fn get_ideal(&self) -> Vec<f32> {
+        match self.variant {
+            ZdtVariant::Zdt3 => {
+                let f2 = self.get_front_f2(ZDT3_SEGMENTS[4].1);
+                vec![0.0, f2 as f32]
+            }
+            ZdtVariant::Zdt6 => vec![ZDT6_MIN_F1 as f32, 0.0],
+            _ => vec![0.0, 0.0],
+        }
+    }
+
+    fn get_front(&self, count: usize) -> Vec<Vec<f32>> {
+        let f1 = match self.variant {
+            ZdtVariant::Zdt3 => get_segmented_linspace(&ZDT3_SEGMENTS, count),
+            ZdtVariant::Zdt6 => get_linspace(ZDT6_MIN_F1, 1.0, count),
+            _ => get_linspace(0.0, 1.0, count),
+        };
+        f1.into_iter()
+            .map(|f1| vec![f1 as f32, self.get_front_f2(f1) as f32])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evolve_with;
+    use crate::indicators::distance;
+    use crate::model::Spea2Model;
+    use crate::problems::Benchmark;
+    use crate::random;
+
+    const VARIANTS: [ZdtVariant; 5] = [
+        ZdtVariant::Zdt1,
+        ZdtVariant::Zdt2,
+        ZdtVariant::Zdt3,
+        ZdtVariant::Zdt4,
+        ZdtVariant::Zdt6,
+    ];
+
+    #[test]
+    fn zdt_optimal_solutions_lie_on_front() {
+        VARIANTS.iter().for_each(|&variant| {
+            let zdt = Zdt::new(variant);
+            [0.0, 0.1, 0.45, 0.83, 1.0].iter().for_each(|&x1| {
+                let mut x = vec![0.0; zdt.variables];
+                x[0] = x1;
+                let values = zdt.evaluate(&x);
+                assert!(
+                    (values[1] - zdt.get_front_f2(values[0])).abs() < 1e-12,
+                    "{:?} {:?}",
+                    variant,
+                    values
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn zdt_evaluate() {
+        let zdt1 = Zdt::new(ZdtVariant::Zdt1);
+        let values = zdt1.evaluate(&[0.25; 30]);
+        assert!((values[0] - 0.25).abs() < 1e-12);
+        assert!((values[1] - 3.25 * (1.0 - (0.25f64 / 3.25).sqrt())).abs() < 1e-12);
+
+        let zdt4 = Zdt::new(ZdtVariant::Zdt4);
+        let mut x = vec![1.0; 10];
+        x[0] = 0.0;
+        assert!((zdt4.evaluate(&x)[1] - 10.0).abs() < 1e-9);
+        assert_eq!(zdt4.get_bounds()[1], Bounds::new(-5.0, 5.0));
+    }
+
+    #[test]
+    fn zdt_get_front() {
+        VARIANTS.iter().for_each(|&variant| {
+            let zdt = Zdt::new(variant);
+            let (ideal, nadir) = (zdt.get_ideal(), zdt.get_nadir());
+            let front = zdt.get_front(100);
+            assert!(front.len() >= 100 - 5, "{:?} {}", variant, front.len());
+            front.iter().for_each(|point| {
+                (0..2).for_each(|i| {
+                    assert!(point[i] >= ideal[i] - 1e-4 && point[i] <= nadir[i] + 1e-4);
+                });
+            });
+            front.windows(2).for_each(|pair| {
+                assert!(pair[0][0] <= pair[1][0] && pair[0][1] >= pair[1][1] - 1e-6);
+            });
+        });
+    }
+
+    #[test]
+    fn zdt_convergence() {
+        [ZdtVariant::Zdt1, ZdtVariant::Zdt2]
+            .iter()
+            .for_each(|&variant| {
+                random::seed(1);
+                let benchmark = Benchmark::new(Zdt::new(variant), 40);
+                let front = benchmark.problem.get_front(200);
+                let mut model = benchmark.get_model();
+                let mut operators = benchmark.get_operators();
+
+                evolve_with(&mut model, &mut operators).unwrap();
+                let objectives = model.objectives.clone();
+                let before =
+                    distance::inverted_generational_distance(&objectives, &model.archive, &front);
+                for _ in 0..100 {
+                    evolve_with(&mut model, &mut operators).unwrap();
+                }
+                let after =
+                    distance::inverted_generational_distance(&objectives, &model.archive, &front);
+
+                assert!(after < before / 2.0, "{:?} {} - {}", variant, before, after);
+            });
+    }
+}
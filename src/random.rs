@@ -0,0 +1,55 @@
+use std::cell::RefCell;
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// A handle to the calling thread's generator, which every operator in the crate draws from.
+/// Draw from it in custom operators too, so that `seed` makes whole runs reproducible.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SeededRng;
+
+impl RngCore for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        RNG.with(|rng| rng.borrow_mut().next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        RNG.with(|rng| rng.borrow_mut().next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        RNG.with(|rng| rng.borrow_mut().fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        RNG.with(|rng| rng.borrow_mut().try_fill_bytes(dest))
+    }
+}
+
+pub fn get_rng() -> SeededRng {
+    SeededRng
+}
+
+/// Reseeds the calling thread's generator. Generators start from entropy otherwise.
+pub fn seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn random_seed() {
+        seed(42);
+        let first: Vec<u32> = (0..10).map(|_| get_rng().gen()).collect();
+        seed(42);
+        let second: Vec<u32> = (0..10).map(|_| get_rng().gen()).collect();
+        assert_eq!(first, second);
+    }
+}
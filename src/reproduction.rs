@@ -1,16 +1,60 @@
-use crate::model::{Model, MutationOperator};
-use crate::{crossover, mutation};
+use crate::model::{Model, ModelItem, MutationOperator, Operators, Variation};
+use crate::random::{self, SeededRng};
+use crate::{crossover, differential, mutation};
 use rand::Rng;
 
 pub fn reproduce(model: &mut Model, mutation: &mut MutationOperator) {
     select_mating_pool(model);
     // crossover::neighbourhood_crossover(model);
-    // mutation::mutate(model, mutation);
+    let mutated = mutation::mutate(model, mutation);
+    model.evaluations += mutated.iter().filter(|mutated| **mutated).count();
+    set_next_population(model);
+}
+
+pub fn reproduce_with(model: &mut Model, operators: &mut Operators) {
+    let changed = match (&operators.variation, operators.portfolio.as_mut()) {
+        (Variation::Genetic, Some(portfolio)) => {
+            select_mating_pool(model);
+            portfolio.apply(model)
+        }
+        (Variation::Genetic, None) => {
+            select_mating_pool(model);
+            let crossed = match operators.crossover.as_mut() {
+                Some(crossover) => crossover::apply_crossover(model, crossover),
+                None => 0,
+            };
+            let mutated = mutation::mutate(model, &mut operators.mutation);
+            mutated
+                .into_iter()
+                .enumerate()
+                .map(|(i, mutated)| i < crossed || mutated)
+                .collect()
+        }
+        (Variation::DifferentialEvolution { f, cr, bounds }, _) => {
+            differential::differential_evolution(model, *f, *cr, bounds)
+        }
+    };
+    model.evaluations += changed.iter().filter(|changed| **changed).count();
+    let objectives = &model.objectives;
+    model
+        .mating_pool
+        .iter_mut()
+        .zip(changed)
+        .filter(|(_, changed)| *changed)
+        .for_each(|(item, _)| {
+            if let Some(repair) = operators.repair.as_mut() {
+                repair(objectives, item);
+            }
+            if let Some(evaluation) = operators.evaluation.as_mut() {
+                evaluation(objectives, item);
+                item.reset_samples();
+            }
+        });
     set_next_population(model);
 }
 
 fn select_mating_pool(model: &mut Model) {
-    // let mut rng = rand::thread_rng();
+    // let mut rng = random::get_rng();
     // let len = model.archive.len();
 
     // for _ in 0..model.population_size {
@@ -30,6 +74,29 @@ fn select_mating_pool(model: &mut Model) {
     model.mating_pool.append(&mut model.archive.clone());
 }
 
+pub fn reproduce_offspring(model: &mut Model, mutation: &mut MutationOperator, batch_size: usize) {
+    let mut rng = random::get_rng();
+    model.mating_pool.clear();
+    for _ in 0..batch_size {
+        let mut offspring = select_by_tournament(&model.archive, &mut rng).clone();
+        mutation(&model.objectives, &mut offspring);
+        offspring.reset_samples();
+        model.mating_pool.push(offspring);
+    }
+    model.evaluations += batch_size;
+    set_next_population(model);
+}
+
+fn select_by_tournament<'a>(archive: &'a [ModelItem], rng: &mut SeededRng) -> &'a ModelItem {
+    let i = rng.gen_range(0..archive.len());
+    let j = rng.gen_range(0..archive.len());
+    if archive[i].fitness <= archive[j].fitness {
+        &archive[i]
+    } else {
+        &archive[j]
+    }
+}
+
 fn set_next_population(model: &mut Model) {
     model.population.clear();
     model.population.append(&mut model.mating_pool);
@@ -39,6 +106,7 @@ fn set_next_population(model: &mut Model) {
 mod tests {
     use super::*;
     use crate::mocks::{self, MOCK_POPULATION_COUNT};
+    use crate::model::{Genome, MutationSchedule, Spea2Model};
 
     #[test]
     fn reproduction_select_mating_pool() {
@@ -51,6 +119,57 @@ mod tests {
         assert_eq!(model.mating_pool.len(), model.population_size);
     }
 
+    #[test]
+    fn reproduction_reproduce_offspring() {
+        let mut model = mocks::get_model_with_archive();
+        let mut spea2_model = mocks::get_spea2model();
+        let mut mutation = spea2_model.get_mutation_operator();
+
+        reproduce_offspring(&mut model, &mut mutation, 2);
+
+        assert_eq!(model.population.len(), 2);
+        assert!(model.mating_pool.is_empty());
+    }
+
+    #[test]
+    fn reproduction_reproduce_with() {
+        let mut model = mocks::get_model_with_real_genomes();
+        model.archive = model.population.clone();
+        model.mutation_schedule = MutationSchedule::Constant(1.0);
+        let mut operators = mocks::get_real_operators();
+
+        reproduce_with(&mut model, &mut operators);
+
+        assert_eq!(model.population.len(), MOCK_POPULATION_COUNT);
+        model.population.iter().for_each(|item| {
+            let mut expected = item.clone();
+            mocks::evaluate_real_genome(&model.objectives, &mut expected);
+            assert_eq!(item.values, expected.values);
+        });
+    }
+
+    #[test]
+    fn reproduction_reproduce_with_repair() {
+        let mut model = mocks::get_model_with_real_genomes();
+        model.archive = model.population.clone();
+        model.mutation_schedule = MutationSchedule::Constant(1.0);
+        let mut operators = mocks::get_real_operators();
+        operators.repair = Some(Box::new(|_, item| {
+            if let Genome::Real(x) = &mut item.genome {
+                x[1] = 0.0;
+            }
+        }));
+
+        reproduce_with(&mut model, &mut operators);
+
+        model.population.iter().for_each(|item| {
+            assert!(matches!(&item.genome, Genome::Real(x) if x[1] == 0.0));
+            let mut expected = item.clone();
+            mocks::evaluate_real_genome(&model.objectives, &mut expected);
+            assert_eq!(item.values, expected.values);
+        });
+    }
+
     #[test]
     fn model_set_next_population() {
         let mut model = mocks::get_model_with_mating_pool();
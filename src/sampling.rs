@@ -0,0 +1,323 @@
+use rand::{prelude::SliceRandom, Rng};
+
+use crate::model::{EvaluationOperator, Genome, ModelItem, Objective};
+use crate::operators::real::Bounds;
+use crate::random;
+
+/// Primitive polynomial degree, coefficients and initial direction numbers for Sobol dimensions
+/// 2 and up, from Joe and Kuo's new-joe-kuo-6.21201 table.
+const SOBOL_DIRECTIONS: [(u32, u32, &[u32]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+const SOBOL_BITS: usize = 32;
+
+#[derive(Debug, Default, Clone)]
+pub enum Sampling {
+    #[default]
+    Uniform,
+    LatinHypercube,
+    Halton,
+    /// Direction numbers cover 21 dimensions; any further dimensions are taken from the Halton
+    /// sequence.
+    Sobol,
+}
+
+/// How to build an initial population of `Genome::Real` items within `bounds`.
+#[derive(Debug, Default, Clone)]
+pub struct Initialisation {
+    pub sampling: Sampling,
+    /// Also evaluate the opposite of every sampled point, `min + max - x`, keeping whichever of
+    /// the two dominates, or the original if neither does.
+    pub opposition_based: bool,
+    /// Known good solutions placed in the population ahead of the sampled ones.
+    pub seeds: Vec<Vec<f64>>,
+}
+
+impl Initialisation {
+    pub fn new(sampling: Sampling) -> Self {
+        Self {
+            sampling,
+            ..Default::default()
+        }
+    }
+
+    pub fn get_population(
+        &self,
+        objectives: &[Objective],
+        bounds: &[Bounds],
+        size: usize,
+        evaluation: &mut EvaluationOperator,
+    ) -> Vec<ModelItem> {
+        let mut get_item = |x: Vec<f64>| {
+            let mut item = ModelItem::new(vec![], None);
+            item.genome = Genome::Real(x);
+            evaluation(objectives, &mut item);
+            item
+        };
+
+        let mut population: Vec<ModelItem> = self
+            .seeds
+            .iter()
+            .take(size)
+            .map(|seed| get_item(seed.clone()))
+            .collect();
+
+        let count = size - population.len();
+        sample(&self.sampling, bounds, count)
+            .into_iter()
+            .for_each(|x| {
+                let item = get_item(x.clone());
+                if !self.opposition_based {
+                    population.push(item);
+                    return;
+                }
+                let opposite = get_item(get_opposite(&x, bounds));
+                match opposite.dominates(&item, objectives) {
+                    true => population.push(opposite),
+                    false => population.push(item),
+                }
+            });
+        population
+    }
+}
+
+/// `count` points within `bounds` spread according to `sampling`.
+pub fn sample(sampling: &Sampling, bounds: &[Bounds], count: usize) -> Vec<Vec<f64>> {
+    let mut rng = random::get_rng();
+    let dimensions = bounds.len();
+    let unit = match sampling {
+        Sampling::Uniform => (0..count)
+            .map(|_| (0..dimensions).map(|_| rng.gen::<f64>()).collect())
+            .collect(),
+        Sampling::LatinHypercube => get_latin_hypercube(dimensions, count, &mut rng),
+        Sampling::Halton => get_halton(dimensions, count),
+        Sampling::Sobol => get_sobol(dimensions, count),
+    };
+    unit.into_iter()
+        .map(|u: Vec<f64>| {
+            u.iter()
+                .zip(bounds)
+                .map(|(u, bound)| bound.min + u * (bound.max - bound.min))
+                .collect()
+        })
+        .collect()
+}
+
+fn get_opposite(x: &[f64], bounds: &[Bounds]) -> Vec<f64> {
+    x.iter()
+        .zip(bounds)
+        .map(|(x, bound)| bound.min + bound.max - x)
+        .collect()
+}
+
+/// One point in each of `count` equal strata of every dimension, paired up at random.
+fn get_latin_hypercube(dimensions: usize, count: usize, rng: &mut impl Rng) -> Vec<Vec<f64>> {
+    let mut points = vec![vec![0.0; dimensions]; count];
+    for d in 0..dimensions {
+        let mut strata: Vec<usize> = (0..count).collect();
+        strata.shuffle(rng);
+        points.iter_mut().zip(strata).for_each(|(point, stratum)| {
+            point[d] = (stratum as f64 + rng.gen::<f64>()) / count as f64
+        });
+    }
+    points
+}
+
+fn get_halton(dimensions: usize, count: usize) -> Vec<Vec<f64>> {
+    let primes = get_primes(dimensions);
+    (1..=count)
+        .map(|i| {
+            primes
+                .iter()
+                .map(|&base| radical_inverse(i, base))
+                .collect()
+        })
+        .collect()
+}
+
+fn radical_inverse(mut i: usize, base: usize) -> f64 {
+    let mut result = 0.0;
+    let mut fraction = 1.0 / base as f64;
+    while i > 0 {
+        result += (i % base) as f64 * fraction;
+        i /= base;
+        fraction /= base as f64;
+    }
+    result
+}
+
+fn get_primes(count: usize) -> Vec<usize> {
+    let mut primes: Vec<usize> = vec![];
+    let mut candidate = 2;
+    while primes.len() < count {
+        if primes.iter().all(|prime| candidate % prime != 0) {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+fn get_sobol(dimensions: usize, count: usize) -> Vec<Vec<f64>> {
+    let sobol_dimensions = dimensions.min(SOBOL_DIRECTIONS.len() + 1);
+    let directions: Vec<Vec<u32>> = (0..sobol_dimensions).map(get_direction_numbers).collect();
+    let halton = match dimensions > sobol_dimensions {
+        true => get_halton(dimensions, count),
+        false => vec![],
+    };
+    let mut x = vec![0u32; sobol_dimensions];
+    let scale = 2f64.powi(SOBOL_BITS as i32);
+    (0..count)
+        .map(|i| {
+            if i > 0 {
+                let c = (i - 1).trailing_ones() as usize;
+                x.iter_mut()
+                    .zip(directions.iter())
+                    .for_each(|(x, v)| *x ^= v[c]);
+            }
+            let extra = halton
+                .get(i)
+                .map_or(&[][..], |point| &point[sobol_dimensions..]);
+            x.iter()
+                .map(|&x| x as f64 / scale)
+                .chain(extra.iter().cloned())
+                .collect()
+        })
+        .collect()
+}
+
+fn get_direction_numbers(dimension: usize) -> Vec<u32> {
+    if dimension == 0 {
+        return (1..=SOBOL_BITS).map(|k| 1 << (SOBOL_BITS - k)).collect();
+    }
+    let (s, a, initial) = SOBOL_DIRECTIONS[dimension - 1];
+    let s = s as usize;
+    let mut m: Vec<u32> = initial.to_vec();
+    for k in s..SOBOL_BITS {
+        let mut next = m[k - s] ^ (m[k - s] << s);
+        for i in 1..s {
+            if (a >> (s - 1 - i)) & 1 == 1 {
+                next ^= m[k - i] << i;
+            }
+        }
+        m.push(next);
+    }
+    m.iter()
+        .enumerate()
+        .map(|(k, &m)| m << (SOBOL_BITS - k - 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks;
+
+    fn get_unit_bounds(dimensions: usize) -> Vec<Bounds> {
+        vec![Bounds::new(0.0, 1.0); dimensions]
+    }
+
+    fn is_stratified(points: &[Vec<f64>], dimension: usize) -> bool {
+        let count = points.len();
+        let mut strata: Vec<usize> = points
+            .iter()
+            .map(|point| (point[dimension] * count as f64) as usize)
+            .collect();
+        strata.sort();
+        strata == (0..count).collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn sampling_sample_in_bounds() {
+        let bounds = vec![Bounds::new(-5.0, 5.0), Bounds::new(10.0, 20.0)];
+        [
+            Sampling::Uniform,
+            Sampling::LatinHypercube,
+            Sampling::Halton,
+            Sampling::Sobol,
+        ]
+        .iter()
+        .for_each(|sampling| {
+            let points = sample(sampling, &bounds, 50);
+            assert_eq!(points.len(), 50);
+            points.iter().for_each(|point| {
+                point
+                    .iter()
+                    .zip(bounds.iter())
+                    .for_each(|(x, bound)| assert!(*x >= bound.min && *x <= bound.max));
+            });
+        });
+    }
+
+    #[test]
+    fn sampling_latin_hypercube() {
+        let points = sample(&Sampling::LatinHypercube, &get_unit_bounds(3), 20);
+        (0..3).for_each(|d| assert!(is_stratified(&points, d)));
+    }
+
+    #[test]
+    fn sampling_halton() {
+        let points = sample(&Sampling::Halton, &get_unit_bounds(2), 3);
+        assert_eq!(
+            points,
+            [[0.5, 1.0 / 3.0], [0.25, 2.0 / 3.0], [0.75, 1.0 / 9.0]]
+        );
+    }
+
+    #[test]
+    fn sampling_sobol() {
+        let points = sample(&Sampling::Sobol, &get_unit_bounds(21), 64);
+        assert_eq!(points[1][0], 0.5);
+        assert_eq!(points[2][1], 0.25);
+        (0..21).for_each(|d| assert!(is_stratified(&points, d), "dimension {}", d));
+    }
+
+    #[test]
+    fn sampling_sobol_beyond_direction_numbers() {
+        let points = sample(&Sampling::Sobol, &get_unit_bounds(30), 64);
+        assert!(points.iter().all(|point| point.len() == 30));
+        assert_eq!(points[1][21], radical_inverse(2, 79));
+        (0..21).for_each(|d| assert!(is_stratified(&points, d), "dimension {}", d));
+        (21..30).for_each(|d| {
+            assert!(points.iter().all(|point| point[d] > 0.0 && point[d] < 1.0));
+        });
+    }
+
+    #[test]
+    fn sampling_get_population() {
+        let objectives = mocks::get_model_basic().objectives;
+        let bounds = mocks::get_real_bounds();
+        let mut evaluation: EvaluationOperator = Box::new(mocks::evaluate_real_genome);
+        let mut initialisation = Initialisation::new(Sampling::LatinHypercube);
+        initialisation.opposition_based = true;
+        initialisation.seeds = vec![vec![1.0, 0.0]];
+
+        let population = initialisation.get_population(&objectives, &bounds, 10, &mut evaluation);
+
+        assert_eq!(population.len(), 10);
+        assert_eq!(population[0].genome, Genome::Real(vec![1.0, 0.0]));
+        assert_eq!(population[0].values, [100.0, 0.0]);
+        population
+            .iter()
+            .for_each(|item| assert_eq!(item.values.len(), objectives.len()));
+    }
+}
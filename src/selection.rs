@@ -3,12 +3,12 @@ use crate::model::{Distance, Model, ModelItem};
 pub fn apply_selection(model: &mut Model) {
     let (mut dominated, mut non_dominated) = drain_model_by_dominance(model);
 
-    dominated.sort_by(|a, b| a.fitness.partial_cmp(&b.fitness).unwrap());
-    non_dominated.sort_by(|a, b| a.fitness.partial_cmp(&b.fitness).unwrap());
+    dominated.sort_by(|a, b| a.fitness.total_cmp(&b.fitness));
+    non_dominated.sort_by(|a, b| a.fitness.total_cmp(&b.fitness));
 
     ensure_archive_size(&mut dominated, &mut non_dominated, model.population_size);
 
-    non_dominated.sort_by(|a, b| a.fitness.partial_cmp(&b.fitness).unwrap());
+    non_dominated.sort_by(|a, b| a.fitness.total_cmp(&b.fitness));
 
     model.archive = non_dominated;
 }
@@ -41,14 +41,14 @@ fn ensure_archive_size(
 
     match nd_len.cmp(&archive_size) {
         std::cmp::Ordering::Less => {
-            dominated.sort_by(|a, b| a.fitness.partial_cmp(&b.fitness).unwrap());
+            dominated.sort_by(|a, b| a.fitness.total_cmp(&b.fitness));
             dominated.truncate(archive_size - nd_len);
             non_dominated.append(dominated);
         }
         std::cmp::Ordering::Greater => {
             while non_dominated.len() > archive_size {
                 distances = get_orderable_distances(non_dominated);
-                distances.sort_by(|a, b| a.value.partial_cmp(&b.value).unwrap());
+                distances.sort_by(|a, b| a.value.total_cmp(&b.value));
                 let closest = get_closest(&distances);
                 non_dominated.remove(closest.from);
             }
@@ -85,7 +85,7 @@ fn get_orderable_distances(dominated: &[ModelItem]) -> Vec<Distance> {
 
 fn get_closest(distances: &[Distance]) -> &Distance {
     if (distances[0].value == distances[1].value) && (distances[0].from != distances[1].from) {
-        match distance_tiebreak(distances, 0, 1) {
+        match distance_tiebreak(distances, distances[0].from, distances[1].from) {
             Some(d) => d,
             None => &distances[0],
         }
@@ -94,6 +94,8 @@ fn get_closest(distances: &[Distance]) -> &Distance {
     }
 }
 
+/// Breaks a tie between the first two distances, whose `from` items are `i` and `j`, by the next
+/// nearest neighbours, returning the tied distance of the more crowded item.
 fn distance_tiebreak(distances: &[Distance], i: usize, j: usize) -> Option<&Distance> {
     let d1: Vec<&Distance> = distances
         .iter()
@@ -104,11 +106,11 @@ fn distance_tiebreak(distances: &[Distance], i: usize, j: usize) -> Option<&Dist
         .filter(|d| d.from == j || d.to == j)
         .collect();
 
-    for i in 1..d1.len() {
-        if d1[i].value < d2[i].value {
-            return Some(d1[i]);
-        } else if d2[i].value < d1[i].value {
-            return Some(d2[i]);
+    for k in 1..d1.len().min(d2.len()) {
+        if d1[k].value < d2[k].value {
+            return Some(&distances[0]);
+        } else if d2[k].value < d1[k].value {
+            return Some(&distances[1]);
         }
     }
     None
@@ -185,9 +187,27 @@ mod tests {
     #[test]
     fn selection_get_closest_with_tiebreak() {
         let mut distances = mocks::get_distances_with_tie();
-        distances.sort_by(|a, b| a.value.partial_cmp(&b.value).unwrap());
+        distances.sort_by(|a, b| a.value.total_cmp(&b.value));
         let closest = get_closest(&distances);
         assert_eq!(closest.from, 1);
         assert_eq!(closest.to, 3);
     }
+
+    #[test]
+    fn selection_get_closest_tiebreak_compares_tied_pair() {
+        let distances = vec![
+            Distance::new(2, 4, 1.0),
+            Distance::new(3, 4, 1.0),
+            Distance::new(0, 2, 2.0),
+            Distance::new(1, 3, 3.0),
+            Distance::new(0, 1, 5.0),
+            Distance::new(0, 3, 6.0),
+            Distance::new(1, 2, 7.0),
+            Distance::new(0, 4, 8.0),
+            Distance::new(1, 4, 9.0),
+            Distance::new(2, 3, 10.0),
+        ];
+        let closest = get_closest(&distances);
+        assert_eq!(closest, &distances[0]);
+    }
 }
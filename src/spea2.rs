@@ -1,66 +1,232 @@
-use model::{Model, MutationOperator};
+use error::Spea2Error;
+use model::{Model, MutationOperator, Operators, Spea2Model};
+use observer::{GenerationStats, Observer, PhaseTimings};
 
+pub mod attainment;
 pub mod canvas;
+pub mod dynamic;
+pub mod error;
+pub mod experiment;
+pub mod indicators;
+pub mod island;
 pub mod mocks;
 pub mod model;
+pub mod noise;
+pub mod observer;
+pub mod operators;
+pub mod portfolio;
+pub mod problems;
+pub mod random;
+pub mod sampling;
 
 mod constants;
 mod crossover;
+mod differential;
+mod duplicates;
 mod fitness;
 mod mutation;
 mod reproduction;
 mod selection;
+mod steady_state;
+mod validation;
 
-pub fn evolve(model: &mut Model, mutation: &mut MutationOperator) {
-    fitness::set_fitness(model);
-    selection::apply_selection(model);
-    reproduction::reproduce(model, mutation);
+pub fn evolve(
+    model: &mut Model,
+    mutation: &mut MutationOperator,
+) -> Result<GenerationStats, Spea2Error> {
+    evolve_observed(model, mutation, &mut ())
+}
+
+/// Like `evolve`, calling `observer` at each phase of the generation.
+pub fn evolve_observed(
+    model: &mut Model,
+    mutation: &mut MutationOperator,
+    observer: &mut dyn Observer,
+) -> Result<GenerationStats, Spea2Error> {
+    let mut timings = PhaseTimings::default();
+    observer::time(&mut timings.validation, || {
+        duplicates::eliminate_duplicates(model, &mut *mutation);
+        validation::validate_population(model)
+    })?;
+    observer.before_fitness(model);
+    observer::time(&mut timings.fitness, || fitness::set_fitness(model));
+    observer::time(&mut timings.selection, || selection::apply_selection(model));
+    observer.after_selection(model);
+    observer::time(&mut timings.reproduction, || {
+        reproduction::reproduce(model, mutation)
+    });
+    observer.after_reproduction(model);
+    end_generation(model, timings, observer)
+}
+
+/// Like `evolve`, but reproduces through a pipeline of crossover, mutation and evaluation.
+pub fn evolve_with(
+    model: &mut Model,
+    operators: &mut Operators,
+) -> Result<GenerationStats, Spea2Error> {
+    evolve_with_observed(model, operators, &mut ())
+}
+
+/// Like `evolve_with`, calling `observer` at each phase of the generation.
+pub fn evolve_with_observed(
+    model: &mut Model,
+    operators: &mut Operators,
+    observer: &mut dyn Observer,
+) -> Result<GenerationStats, Spea2Error> {
+    let mut timings = PhaseTimings::default();
+    observer::time(&mut timings.validation, || {
+        let Operators {
+            mutation,
+            repair,
+            evaluation,
+            ..
+        } = &mut *operators;
+        let mut replacement: MutationOperator = Box::new(|objectives, item| {
+            mutation(objectives, item);
+            if let Some(repair) = repair.as_mut() {
+                repair(objectives, item);
+            }
+            if let Some(evaluation) = evaluation.as_mut() {
+                evaluation(objectives, item);
+            }
+        });
+        duplicates::eliminate_duplicates(model, &mut replacement);
+        drop(replacement);
+        validation::validate_population(model)
+    })?;
+    observer.before_fitness(model);
+    observer::time(&mut timings.fitness, || fitness::set_fitness(model));
+    observer::time(&mut timings.selection, || {
+        selection::apply_selection(model);
+        if let Some(portfolio) = operators.portfolio.as_mut() {
+            portfolio.assign_credit(model);
+        }
+    });
+    observer.after_selection(model);
+    observer::time(&mut timings.reproduction, || {
+        reproduction::reproduce_with(model, operators)
+    });
+    observer.after_reproduction(model);
+    end_generation(model, timings, observer)
+}
+
+fn end_generation(
+    model: &mut Model,
+    timings: PhaseTimings,
+    observer: &mut dyn Observer,
+) -> Result<GenerationStats, Spea2Error> {
+    model.generation += 1;
+    let stats = GenerationStats::new(model, timings);
+    observer.generation_end(model, &stats);
+    Ok(stats)
+}
+
+/// Replaces items that duplicate an earlier archive or population member, according to
+/// `Model::duplicate_detection`, and returns how many were replaced. `evolve` does this with its
+/// mutation operator; call this or `eliminate_duplicates_with_random` first to replace duplicates
+/// another way.
+pub fn eliminate_duplicates(model: &mut Model, replacement: &mut MutationOperator) -> usize {
+    duplicates::eliminate_duplicates(model, replacement)
+}
+
+/// Like `eliminate_duplicates`, replacing duplicates with fresh `Spea2Model::get_random_item`
+/// individuals. Duplicates are kept when the model has no random items.
+pub fn eliminate_duplicates_with_random<S: Spea2Model>(
+    model: &mut Model,
+    spea2_model: &mut S,
+) -> usize {
+    duplicates::eliminate_duplicates(model, |objectives, item| {
+        if let Some(random) = spea2_model.get_random_item(objectives) {
+            *item = random;
+        }
+    })
+}
+
+/// Breeds and inserts `batch_size` offspring at a time, so the archive is up to date after every
+/// batch of evaluations. The first call only selects the initial population into the archive.
+/// Strengths, raw fitness and neighbour distances are kept between calls and updated for the
+/// inserted and removed items only; the archive is not kept sorted by fitness. `generation` is
+/// left unchanged.
+pub fn evolve_steady_state(
+    model: &mut Model,
+    mutation: &mut MutationOperator,
+    batch_size: usize,
+) -> Result<GenerationStats, Spea2Error> {
+    evolve_steady_state_observed(model, mutation, batch_size, &mut ())
+}
+
+/// Like `evolve_steady_state`, calling `observer` at each phase of the batch.
+pub fn evolve_steady_state_observed(
+    model: &mut Model,
+    mutation: &mut MutationOperator,
+    batch_size: usize,
+    observer: &mut dyn Observer,
+) -> Result<GenerationStats, Spea2Error> {
+    let mut timings = PhaseTimings::default();
+    let initial = model.archive.is_empty();
+    if !initial {
+        observer::time(&mut timings.reproduction, || {
+            reproduction::reproduce_offspring(model, mutation, batch_size)
+        });
+        observer.after_reproduction(model);
+    }
+    observer::time(&mut timings.validation, || {
+        duplicates::eliminate_duplicates(model, &mut *mutation);
+        validation::validate_population(model)
+    })?;
+    observer.before_fitness(model);
+    if initial {
+        observer::time(&mut timings.fitness, || fitness::set_fitness(model));
+        observer::time(&mut timings.selection, || selection::apply_selection(model));
+    } else {
+        observer::time(&mut timings.fitness, || {
+            steady_state::insert_population(model)
+        });
+        observer::time(&mut timings.selection, || {
+            steady_state::truncate_archive(model)
+        });
+    }
+    observer.after_selection(model);
+    let stats = GenerationStats::new(model, timings);
+    observer.generation_end(model, &stats);
+    Ok(stats)
 }
 
 #[cfg(test)]
 mod tests {
     use std::time::Instant;
 
-    use crate::model::{Direction, Spea2Model};
+    use crate::indicators::distance;
+    use crate::mocks::{MOCK_MAX_OBJECTIVE_VALUE, MOCK_MIN_OBJECTIVE_VALUE};
+    use crate::model::{MutationSchedule, Spea2Model, Variation};
+    use crate::operators::real;
+    use crate::portfolio::{CreditAssignment, Portfolio, PortfolioOperator};
 
     use super::*;
 
     #[test]
-    fn spea2_evolve_average_archive_values() {
+    fn spea2_evolve_convergence() {
         let mut spea2_model = mocks::get_spea2model();
         let mut model = spea2_model.get_model();
         let mut mutation = spea2_model.get_mutation_operator();
+        let front = vec![vec![MOCK_MAX_OBJECTIVE_VALUE, MOCK_MIN_OBJECTIVE_VALUE]];
         let gen = 100;
 
-        println!("Pop: {}", model.population_size);
-        println!("Gen: {}", gen);
-
         //run once to create an archive
-        evolve(&mut model, &mut mutation);
+        evolve(&mut model, &mut mutation).unwrap();
 
-        let before = model.get_average_archive_values();
+        let igd = |model: &Model| {
+            distance::inverted_generational_distance(&model.objectives, &model.archive, &front)
+        };
+        let before = igd(&model);
 
-        let start = Instant::now();
-        let mut success = true;
-
-        (0..gen).for_each(|count| {
-            evolve(&mut model, &mut mutation);
-            let after = model.get_average_archive_values();
-            model.objectives.iter().for_each(|objective| {
-                let i = objective.index;
-                let dir = &objective.direction;
-                match dir {
-                    Direction::Maximised => success = before[i] <= after[i],
-                    Direction::Minimised => success = before[i] >= after[i],
-                };
-                assert!(
-                    success,
-                    "{}. {:#?}({}): {} - {}",
-                    count, dir, i, before[i], after[i]
-                );
-            });
+        (0..gen).for_each(|_| {
+            evolve(&mut model, &mut mutation).unwrap();
         });
-        println!("duration: {:?}", start.elapsed());
+
+        let after = igd(&model);
+        assert!(after < before, "{} - {}", before, after);
+        assert!(after < 0.1, "{}", after);
     }
 
     #[test]
@@ -74,13 +240,13 @@ mod tests {
         println!("Gen: {}", gen);
 
         //run once to initialse
-        evolve(&mut model, &mut mutation);
+        evolve(&mut model, &mut mutation).unwrap();
 
         let before = model.get_average_fitness();
 
         let start = Instant::now();
         (0..gen).for_each(|_| {
-            evolve(&mut model, &mut mutation);
+            evolve(&mut model, &mut mutation).unwrap();
             let after = model.get_average_fitness();
             println!("{} - {}", before, after);
             // assert!(after <= before);
@@ -88,4 +254,211 @@ mod tests {
 
         println!("duration: {:?}", start.elapsed());
     }
+
+    #[test]
+    fn spea2_evolve_with() {
+        let mut model = mocks::get_model_with_real_genomes();
+        let mut operators = mocks::get_real_operators();
+
+        (0..20).for_each(|_| {
+            evolve_with(&mut model, &mut operators).unwrap();
+        });
+
+        assert_eq!(model.archive.len(), model.population_size);
+        assert_eq!(model.population.len(), model.population_size);
+        assert_eq!(model.generation, 20);
+    }
+
+    #[test]
+    fn spea2_evolve_with_convergence() {
+        let mut model = mocks::get_model_with_real_genomes();
+        let mut operators = mocks::get_real_operators();
+        let front = vec![vec![MOCK_MAX_OBJECTIVE_VALUE, MOCK_MIN_OBJECTIVE_VALUE]];
+        let objectives = model.objectives.clone();
+
+        evolve_with(&mut model, &mut operators).unwrap();
+        let gd_before = distance::generational_distance(&objectives, &model.archive, &front);
+        let igd_before =
+            distance::inverted_generational_distance(&objectives, &model.archive, &front);
+
+        (0..100).for_each(|_| {
+            evolve_with(&mut model, &mut operators).unwrap();
+        });
+
+        let archive = &model.archive;
+        let gd = distance::generational_distance(&objectives, archive, &front);
+        let igd = distance::inverted_generational_distance(&objectives, archive, &front);
+        let igd_plus = distance::inverted_generational_distance_plus(&objectives, archive, &front);
+        let hausdorff = distance::averaged_hausdorff_distance(&objectives, archive, &front, 2.0);
+        assert!(gd < gd_before, "{} - {}", gd_before, gd);
+        assert!(igd < igd_before, "{} - {}", igd_before, igd);
+        assert!(igd_plus <= igd);
+        assert!(hausdorff >= gd);
+    }
+
+    #[test]
+    fn spea2_evolve_with_differential_evolution() {
+        let mut model = mocks::get_model_with_real_genomes();
+        let mut operators = mocks::get_real_operators();
+        operators.variation = Variation::DifferentialEvolution {
+            f: 0.5,
+            cr: 0.9,
+            bounds: mocks::get_real_bounds(),
+        };
+
+        (0..20).for_each(|_| {
+            evolve_with(&mut model, &mut operators).unwrap();
+        });
+
+        assert_eq!(model.archive.len(), model.population_size);
+        assert_eq!(model.population.len(), model.population_size);
+    }
+
+    #[test]
+    fn spea2_evolve_with_portfolio() {
+        let mut model = mocks::get_model_with_real_genomes();
+        let mut operators = mocks::get_real_operators();
+        let bounds = mocks::get_real_bounds();
+        operators.portfolio = Some(Portfolio::new(
+            vec![
+                PortfolioOperator::new(
+                    "sbx",
+                    Some(real::get_sbx_crossover_operator(bounds.clone(), 15.0)),
+                    real::get_polynomial_mutation_operator(bounds.clone(), 20.0, 0.5),
+                ),
+                PortfolioOperator::new(
+                    "polynomial",
+                    None,
+                    real::get_polynomial_mutation_operator(bounds, 20.0, 0.5),
+                ),
+            ],
+            CreditAssignment::default(),
+        ));
+
+        (0..20).for_each(|_| {
+            evolve_with(&mut model, &mut operators).unwrap();
+        });
+
+        let portfolio = operators.portfolio.unwrap();
+        assert_eq!(portfolio.history.len(), 19);
+        assert!((portfolio.probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(model.archive.len(), model.population_size);
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        phases: Vec<&'static str>,
+        evaluations: Vec<usize>,
+    }
+
+    impl Observer for RecordingObserver {
+        fn before_fitness(&mut self, _: &Model) {
+            self.phases.push("before_fitness");
+        }
+        fn after_selection(&mut self, model: &Model) {
+            assert_eq!(model.archive.len(), model.population_size);
+            self.phases.push("after_selection");
+        }
+        fn after_reproduction(&mut self, _: &Model) {
+            self.phases.push("after_reproduction");
+        }
+        fn generation_end(&mut self, model: &Model, stats: &GenerationStats) {
+            assert_eq!(stats.generation, model.generation);
+            self.phases.push("generation_end");
+            self.evaluations.push(stats.evaluations);
+        }
+    }
+
+    #[test]
+    fn spea2_evolve_with_observed() {
+        let mut model = mocks::get_model_with_real_genomes();
+        model.mutation_schedule = MutationSchedule::Constant(1.0);
+        model.reference_point = Some(vec![MOCK_MIN_OBJECTIVE_VALUE, MOCK_MAX_OBJECTIVE_VALUE]);
+        let mut operators = mocks::get_real_operators();
+        let mut observer = RecordingObserver::default();
+
+        let stats = (0..3)
+            .map(|_| evolve_with_observed(&mut model, &mut operators, &mut observer).unwrap())
+            .last()
+            .unwrap();
+
+        assert_eq!(
+            observer.phases[..4],
+            [
+                "before_fitness",
+                "after_selection",
+                "after_reproduction",
+                "generation_end"
+            ]
+        );
+        assert_eq!(observer.phases.len(), 12);
+        assert_eq!(observer.evaluations, [10, 20, 30]);
+        assert_eq!(stats.archive_size, model.population_size);
+        assert!(stats.non_dominated_count > 0);
+        assert!(stats.hypervolume.unwrap() > 0.0);
+        assert_eq!(stats.effective_mutation_rate, 1.0);
+    }
+
+    #[test]
+    fn spea2_evolve_steady_state() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = spea2_model.get_model();
+        let mut mutation = spea2_model.get_mutation_operator();
+
+        evolve_steady_state(&mut model, &mut mutation, 2).unwrap();
+        assert_eq!(model.archive.len(), model.population_size);
+        assert!(model.population.is_empty());
+
+        (0..50).for_each(|_| {
+            evolve_steady_state(&mut model, &mut mutation, 2).unwrap();
+            assert_eq!(model.archive.len(), model.population_size);
+            assert!(model.population.is_empty());
+        });
+    }
+
+    #[test]
+    fn spea2_evolve_steady_state_evaluations() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut generational = spea2_model.get_model();
+        let mut steady_state = generational.clone();
+        let mut mutation = spea2_model.get_mutation_operator();
+        generational.mutation_schedule = MutationSchedule::Constant(1.0);
+
+        evolve(&mut generational, &mut mutation).unwrap();
+        evolve(&mut generational, &mut mutation).unwrap();
+        evolve_steady_state(&mut steady_state, &mut mutation, 2).unwrap();
+        evolve_steady_state(&mut steady_state, &mut mutation, 2).unwrap();
+
+        assert_eq!(generational.evaluations, 2 * generational.population_size);
+        assert_eq!(steady_state.evaluations, 2);
+        assert_eq!(steady_state.generation, 0);
+    }
+
+    #[test]
+    fn spea2_evolve_steady_state_observed() {
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = spea2_model.get_model();
+        let mut mutation = spea2_model.get_mutation_operator();
+        let mut observer = RecordingObserver::default();
+
+        evolve_steady_state_observed(&mut model, &mut mutation, 2, &mut observer).unwrap();
+        let stats =
+            evolve_steady_state_observed(&mut model, &mut mutation, 2, &mut observer).unwrap();
+
+        assert_eq!(
+            observer.phases,
+            [
+                "before_fitness",
+                "after_selection",
+                "generation_end",
+                "after_reproduction",
+                "before_fitness",
+                "after_selection",
+                "generation_end"
+            ]
+        );
+        assert_eq!(observer.evaluations, [0, 2]);
+        assert_eq!(stats.archive_size, model.population_size);
+        assert_eq!(stats.generation, 0);
+    }
 }
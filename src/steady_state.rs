@@ -0,0 +1,305 @@
+use crate::fitness::{get_distance, get_dominance};
+use crate::model::{Model, ModelItem};
+
+/// Dominance and nearest neighbour distances among the archive, kept between steady-state batches
+/// so that offspring can be inserted and the archive truncated without reassigning fitness to
+/// every item.
+#[derive(Debug, Default, Clone)]
+pub struct ArchiveCache {
+    /// The values, variances and samples of each archive item, to detect an archive changed elsewhere.
+    keys: Vec<(Vec<f32>, Vec<f32>, usize)>,
+    dominance_confidence: f32,
+    /// `dominates[i][j]` when item `i` dominates item `j`.
+    dominates: Vec<Vec<bool>>,
+    strengths: Vec<usize>,
+    raw_fitness: Vec<usize>,
+    /// The smallest distances from each item, including its distance of 0 to itself, ascending.
+    neighbours: Vec<Vec<f32>>,
+    /// Whether an item's `neighbours` still hold its distance to every other item.
+    exhaustive: Vec<bool>,
+    capacity: usize,
+}
+
+/// Moves the population into the archive, updating strengths, raw fitness and neighbour distances
+/// for the new items only, then assigns fitness over the enlarged archive as `set_fitness` would.
+pub fn insert_population(model: &mut Model) {
+    let mut cache = std::mem::take(&mut model.archive_cache);
+    let mut archive = std::mem::take(&mut model.archive);
+    let population: Vec<ModelItem> = model.population.drain(..).collect();
+    cache.capacity = get_capacity(archive.len() + population.len());
+
+    if !cache.is_current(model, &archive) {
+        cache = ArchiveCache {
+            dominance_confidence: model.dominance_confidence,
+            capacity: cache.capacity,
+            ..Default::default()
+        };
+        let items = std::mem::take(&mut archive);
+        items
+            .into_iter()
+            .for_each(|item| cache.push(model, &mut archive, item));
+    }
+    population
+        .into_iter()
+        .for_each(|item| cache.push(model, &mut archive, item));
+    cache.set_fitness(&mut archive);
+
+    model.archive = archive;
+    model.archive_cache = cache;
+}
+
+/// Removes archive items until `Model::population_size` remain, as `apply_selection` does: the
+/// dominated items with the highest fitness go first, then the non-dominated items closest to
+/// their neighbours. Strengths and neighbour distances are updated after every removal.
+pub fn truncate_archive(model: &mut Model) {
+    let mut cache = std::mem::take(&mut model.archive_cache);
+    let archive = &mut model.archive;
+    let excess = archive.len().saturating_sub(model.population_size);
+    let mut dominated: Vec<usize> = (0..archive.len())
+        .filter(|&i| cache.raw_fitness[i] > 0)
+        .collect();
+
+    let mut removed = if dominated.len() > excess {
+        dominated.sort_by(|&a, &b| archive[a].fitness.total_cmp(&archive[b].fitness));
+        dominated.split_off(dominated.len() - excess)
+    } else {
+        dominated
+    };
+    removed.sort_unstable_by(|a, b| b.cmp(a));
+    removed.into_iter().for_each(|r| cache.remove(archive, r));
+
+    while archive.len() > model.population_size {
+        let crowded = cache.get_most_crowded(archive);
+        cache.remove(archive, crowded);
+    }
+    cache.set_fitness(archive);
+    model.archive_cache = cache;
+}
+
+fn get_capacity(len: usize) -> usize {
+    (len as f64).sqrt() as usize + 1
+}
+
+fn get_key(item: &ModelItem) -> (Vec<f32>, Vec<f32>, usize) {
+    (item.values.clone(), item.variances.clone(), item.samples)
+}
+
+impl ArchiveCache {
+    fn is_current(&self, model: &Model, archive: &[ModelItem]) -> bool {
+        self.dominance_confidence == model.dominance_confidence
+            && self.keys.len() == archive.len()
+            && self
+                .keys
+                .iter()
+                .zip(archive)
+                .all(|(key, item)| *key == get_key(item))
+    }
+
+    fn push(&mut self, model: &Model, archive: &mut Vec<ModelItem>, item: ModelItem) {
+        let len = archive.len();
+        let mut distances = vec![0.0];
+        let mut dominated_by = vec![false; len];
+        let mut dominates = vec![false; len + 1];
+        for i in 0..len {
+            let distance = get_distance(&archive[i], &item);
+            self.insert_distance(i, distance);
+            distances.push(distance);
+            (dominated_by[i], dominates[i]) = get_dominance(
+                &model.objectives,
+                model.dominance_confidence,
+                &archive[i],
+                &item,
+            );
+        }
+
+        let strength = dominates.iter().filter(|&&d| d).count();
+        let mut raw_fitness = 0;
+        for i in (0..len).filter(|&i| dominated_by[i]) {
+            self.strengths[i] += 1;
+            raw_fitness += self.strengths[i];
+            for l in (0..len).filter(|&l| self.dominates[i][l]) {
+                self.raw_fitness[l] += 1;
+            }
+        }
+        for l in (0..len).filter(|&l| dominates[l]) {
+            self.raw_fitness[l] += strength;
+        }
+
+        self.dominates
+            .iter_mut()
+            .zip(dominated_by)
+            .for_each(|(row, d)| row.push(d));
+        self.dominates.push(dominates);
+        self.strengths.push(strength);
+        self.raw_fitness.push(raw_fitness);
+        distances.sort_by(|a, b| a.total_cmp(b));
+        self.exhaustive.push(distances.len() <= self.capacity);
+        distances.truncate(self.capacity);
+        self.neighbours.push(distances);
+        self.keys.push(get_key(&item));
+        archive.push(item);
+    }
+
+    fn remove(&mut self, archive: &mut Vec<ModelItem>, r: usize) {
+        let len = archive.len();
+        for i in (0..len).filter(|&i| self.dominates[r][i]) {
+            self.raw_fitness[i] -= self.strengths[r];
+        }
+        for j in (0..len).filter(|&j| self.dominates[j][r]) {
+            self.strengths[j] -= 1;
+            for l in (0..len).filter(|&l| l != r && self.dominates[j][l]) {
+                self.raw_fitness[l] -= 1;
+            }
+        }
+        for i in (0..len).filter(|&i| i != r) {
+            let distance = get_distance(&archive[i], &archive[r]);
+            self.remove_distance(i, distance);
+        }
+
+        self.dominates.swap_remove(r);
+        self.dominates.iter_mut().for_each(|row| {
+            row.swap_remove(r);
+        });
+        self.strengths.swap_remove(r);
+        self.raw_fitness.swap_remove(r);
+        self.neighbours.swap_remove(r);
+        self.exhaustive.swap_remove(r);
+        self.keys.swap_remove(r);
+        archive.swap_remove(r);
+    }
+
+    fn insert_distance(&mut self, i: usize, distance: f32) {
+        let row = &mut self.neighbours[i];
+        if !self.exhaustive[i] && row.last().is_none_or(|&last| distance >= last) {
+            return;
+        }
+        let position = row.partition_point(|&d| d <= distance);
+        row.insert(position, distance);
+        if row.len() > self.capacity {
+            row.pop();
+            self.exhaustive[i] = false;
+        }
+    }
+
+    fn remove_distance(&mut self, i: usize, distance: f32) {
+        let row = &mut self.neighbours[i];
+        let position = row.partition_point(|&d| d < distance);
+        if row.get(position) == Some(&distance) {
+            row.remove(position);
+        }
+    }
+
+    /// Recomputes the neighbours of `i` when distances beyond the first `count` were dropped.
+    fn ensure_neighbours(&mut self, archive: &[ModelItem], i: usize, count: usize) {
+        if self.exhaustive[i] || self.neighbours[i].len() >= count {
+            return;
+        }
+        let mut distances: Vec<f32> = archive
+            .iter()
+            .map(|item| get_distance(&archive[i], item))
+            .collect();
+        distances.sort_by(|a, b| a.total_cmp(b));
+        let kept = self.capacity.max(count);
+        self.exhaustive[i] = distances.len() <= kept;
+        distances.truncate(kept);
+        self.neighbours[i] = distances;
+    }
+
+    /// The non-dominated item whose distances to its nearest neighbours are lexicographically
+    /// smallest.
+    fn get_most_crowded(&mut self, archive: &[ModelItem]) -> usize {
+        (0..archive.len()).for_each(|i| self.ensure_neighbours(archive, i, self.capacity));
+        (0..archive.len())
+            .filter(|&i| self.raw_fitness[i] == 0)
+            .min_by(|&a, &b| {
+                let (a, b) = (&self.neighbours[a], &self.neighbours[b]);
+                a.iter()
+                    .zip(b)
+                    .skip(1)
+                    .map(|(a, b)| a.total_cmp(b))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap_or(0)
+    }
+
+    fn set_fitness(&mut self, archive: &mut [ModelItem]) {
+        let kth = (archive.len() as f64).sqrt() as usize;
+        for i in 0..archive.len() {
+            self.ensure_neighbours(archive, i, kth + 1);
+            let row = &self.neighbours[i];
+            let density_estimate = 1.0 / (row[kth.min(row.len() - 1)] + 2.0);
+            archive[i].fitness = self.raw_fitness[i] as f32 + density_estimate;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fitness;
+    use crate::mocks;
+    use crate::model::Spea2Model;
+    use crate::reproduction;
+
+    fn get_fitness(model: &Model) -> Vec<f32> {
+        model.archive.iter().map(|item| item.fitness).collect()
+    }
+
+    #[test]
+    fn steady_state_insert_population_matches_set_fitness() {
+        crate::random::seed(5);
+        let mut model = mocks::get_spea2model().get_model();
+        model.archive = model.population.split_off(model.population.len() / 2);
+        insert_population(&mut model);
+        model.population = mocks::get_rnd_model_item_vec(&model.objectives);
+        insert_population(&mut model);
+
+        let mut expected = model.clone();
+        fitness::set_fitness(&mut expected);
+        assert_eq!(get_fitness(&model), get_fitness(&expected));
+        assert!(model.population.is_empty());
+    }
+
+    #[test]
+    fn steady_state_cache_matches_rebuilt_cache() {
+        crate::random::seed(6);
+        let mut spea2_model = mocks::get_spea2model();
+        let mut model = spea2_model.get_model();
+        let mut mutation = spea2_model.get_mutation_operator();
+        insert_population(&mut model);
+        truncate_archive(&mut model);
+
+        for _ in 0..10 {
+            reproduction::reproduce_offspring(&mut model, &mut mutation, 3);
+            insert_population(&mut model);
+            truncate_archive(&mut model);
+            assert_eq!(model.archive.len(), model.population_size);
+
+            let mut rebuilt = model.clone();
+            rebuilt.archive_cache = ArchiveCache::default();
+            insert_population(&mut rebuilt);
+            assert_eq!(
+                model.archive_cache.strengths,
+                rebuilt.archive_cache.strengths
+            );
+            assert_eq!(
+                model.archive_cache.raw_fitness,
+                rebuilt.archive_cache.raw_fitness
+            );
+            assert_eq!(get_fitness(&model), get_fitness(&rebuilt));
+        }
+    }
+
+    #[test]
+    fn steady_state_truncate_archive_removes_dominated_first() {
+        let mut model = mocks::get_model_basic();
+        model.population_size = 2;
+        insert_population(&mut model);
+        truncate_archive(&mut model);
+
+        assert_eq!(model.archive.len(), 2);
+        assert!(model.archive.iter().all(|item| item.fitness < 1.0));
+        assert!(model.archive.iter().all(|item| item.values != [0.0, 3.0]));
+    }
+}
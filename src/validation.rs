@@ -0,0 +1,229 @@
+use crate::error::Spea2Error;
+use crate::model::{BoundsPolicy, Direction, Model, ModelItem, NonFinitePolicy, Objective};
+
+pub fn validate_population(model: &mut Model) -> Result<(), Spea2Error> {
+    let expected = model.objectives.len();
+    if let Some(item) = model
+        .population
+        .iter()
+        .find(|item| item.values.len() != expected)
+    {
+        return Err(Spea2Error::ValueCountMismatch {
+            expected,
+            found: item.values.len(),
+        });
+    }
+
+    if let NonFinitePolicy::Fail = model.non_finite_policy {
+        if let Some((objective, value)) = model.population.iter().find_map(find_non_finite) {
+            return Err(Spea2Error::NonFiniteValue { objective, value });
+        }
+    }
+
+    let mut valid: Vec<ModelItem> = vec![];
+    for mut item in model.population.drain(..) {
+        match find_non_finite(&item) {
+            None => valid.push(item),
+            Some(_) => match model.non_finite_policy {
+                NonFinitePolicy::Fail | NonFinitePolicy::Reject => (),
+                NonFinitePolicy::Penalise => {
+                    penalise(&model.objectives, &mut item);
+                    valid.push(item);
+                }
+                NonFinitePolicy::Quarantine => model.quarantine.push(item),
+            },
+        }
+    }
+    model.population = valid;
+
+    enforce_bounds(model);
+
+    if model.population.is_empty() && model.archive.is_empty() {
+        return Err(Spea2Error::EmptyModel);
+    }
+    Ok(())
+}
+
+/// Counts every out-of-bounds value, then drops the item if any objective rejects it, or applies
+/// each objective's policy otherwise.
+fn enforce_bounds(model: &mut Model) {
+    model.bounds_violations = vec![0; model.objectives.len()];
+    let objectives = &mut model.objectives;
+    let violations = &mut model.bounds_violations;
+    model.population.retain_mut(|item| {
+        let violated: Vec<&mut Objective> = objectives
+            .iter_mut()
+            .filter(|objective| {
+                let value = item.values[objective.index];
+                value < objective.min || value > objective.max
+            })
+            .collect();
+        violated
+            .iter()
+            .for_each(|objective| violations[objective.index] += 1);
+        if violated
+            .iter()
+            .any(|objective| matches!(objective.bounds_policy, BoundsPolicy::Reject))
+        {
+            return false;
+        }
+
+        violated.into_iter().for_each(|objective| {
+            let value = &mut item.values[objective.index];
+            match objective.bounds_policy {
+                BoundsPolicy::Ignore | BoundsPolicy::Reject => (),
+                BoundsPolicy::Clamp => *value = value.clamp(objective.min, objective.max),
+                BoundsPolicy::Penalise => {
+                    let excess = (*value - value.clamp(objective.min, objective.max)).abs();
+                    *value = match objective.direction {
+                        Direction::Maximised => objective.min - excess,
+                        Direction::Minimised => objective.max + excess,
+                    };
+                }
+                BoundsPolicy::Expand => {
+                    objective.min = objective.min.min(*value);
+                    objective.max = objective.max.max(*value);
+                }
+            }
+        });
+        true
+    });
+}
+
+fn find_non_finite(item: &ModelItem) -> Option<(usize, f32)> {
+    item.values
+        .iter()
+        .enumerate()
+        .find(|(_, value)| !value.is_finite())
+        .map(|(i, value)| (i, *value))
+}
+
+fn penalise(objectives: &[Objective], item: &mut ModelItem) {
+    objectives.iter().for_each(|objective| {
+        let value = &mut item.values[objective.index];
+        if !value.is_finite() {
+            *value = match objective.direction {
+                Direction::Maximised => objective.min,
+                Direction::Minimised => objective.max,
+            };
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{self, MOCK_MAX_OBJECTIVE_VALUE, MOCK_MIN_OBJECTIVE_VALUE};
+
+    #[test]
+    fn validation_fail() {
+        let mut model = mocks::get_model_with_non_finite_values();
+        let result = validate_population(&mut model);
+        assert_eq!(
+            result,
+            Err(Spea2Error::NonFiniteValue {
+                objective: 1,
+                value: f32::INFINITY
+            })
+        );
+    }
+
+    #[test]
+    fn validation_reject() {
+        let mut model = mocks::get_model_with_non_finite_values();
+        model.non_finite_policy = NonFinitePolicy::Reject;
+        validate_population(&mut model).unwrap();
+        assert_eq!(model.population.len(), 1);
+        assert!(model.quarantine.is_empty());
+    }
+
+    #[test]
+    fn validation_penalise() {
+        let mut model = mocks::get_model_with_non_finite_values();
+        model.non_finite_policy = NonFinitePolicy::Penalise;
+        validate_population(&mut model).unwrap();
+        assert_eq!(model.population.len(), 3);
+        assert_eq!(model.population[1].values[1], MOCK_MAX_OBJECTIVE_VALUE);
+        assert_eq!(model.population[2].values[0], MOCK_MIN_OBJECTIVE_VALUE);
+    }
+
+    #[test]
+    fn validation_quarantine() {
+        let mut model = mocks::get_model_with_non_finite_values();
+        model.non_finite_policy = NonFinitePolicy::Quarantine;
+        validate_population(&mut model).unwrap();
+        assert_eq!(model.population.len(), 1);
+        assert_eq!(model.quarantine.len(), 2);
+    }
+
+    #[test]
+    fn validation_fail_keeps_population() {
+        let mut model = mocks::get_model_with_non_finite_values();
+        assert!(validate_population(&mut model).is_err());
+        assert_eq!(model.population.len(), 3);
+    }
+
+    #[test]
+    fn validation_bounds() {
+        let mut model = mocks::get_model_with_out_of_bounds_values();
+        validate_population(&mut model).unwrap();
+        assert_eq!(model.bounds_violations, [1, 1]);
+        assert_eq!(model.population[1].values, [150.0, -20.0]);
+
+        let policies = [
+            (BoundsPolicy::Clamp, [100.0, 0.0]),
+            (BoundsPolicy::Penalise, [-50.0, 120.0]),
+        ];
+        policies.into_iter().for_each(|(policy, expected)| {
+            let mut model = mocks::get_model_with_out_of_bounds_values();
+            model.objectives[0].bounds_policy = policy.clone();
+            model.objectives[1].bounds_policy = policy;
+            validate_population(&mut model).unwrap();
+            assert_eq!(model.population[1].values, expected);
+        });
+
+        let mut model = mocks::get_model_with_out_of_bounds_values();
+        model.objectives[1].bounds_policy = BoundsPolicy::Reject;
+        validate_population(&mut model).unwrap();
+        assert_eq!(model.population.len(), 1);
+
+        let mut model = mocks::get_model_with_out_of_bounds_values();
+        model.objectives[0].bounds_policy = BoundsPolicy::Expand;
+        validate_population(&mut model).unwrap();
+        assert_eq!(model.objectives[0].max, 150.0);
+        assert_eq!(model.population[1].values, [150.0, -20.0]);
+    }
+
+    #[test]
+    fn validation_bounds_rejected_items_count_every_violation() {
+        let mut model = mocks::get_model_with_out_of_bounds_values();
+        model.objectives[0].bounds_policy = BoundsPolicy::Reject;
+        model.objectives[1].bounds_policy = BoundsPolicy::Expand;
+        validate_population(&mut model).unwrap();
+        assert_eq!(model.population.len(), 1);
+        assert_eq!(model.bounds_violations, [1, 1]);
+        assert_eq!(model.objectives[1].min, MOCK_MIN_OBJECTIVE_VALUE);
+    }
+
+    #[test]
+    fn validation_value_count_mismatch() {
+        let mut model = mocks::get_model_basic();
+        model.population[0].values.pop();
+        let result = validate_population(&mut model);
+        assert_eq!(
+            result,
+            Err(Spea2Error::ValueCountMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn validation_empty_model() {
+        let mut model = mocks::get_model_with_non_finite_values();
+        model.population.remove(0);
+        model.non_finite_policy = NonFinitePolicy::Reject;
+        assert_eq!(validate_population(&mut model), Err(Spea2Error::EmptyModel));
+    }
+}
@@ -0,0 +1,53 @@
+use spea2::evolve_with;
+use spea2::indicators::distance;
+use spea2::model::Spea2Model;
+use spea2::problems::dtlz::{Dtlz, DtlzVariant};
+use spea2::problems::{Benchmark, Problem};
+use spea2::random;
+
+const POPULATION_SIZE: usize = 40;
+const GENERATIONS: usize = 50;
+
+/// IGD against the reference front before and after a short evolution.
+fn get_igd(variant: DtlzVariant, objectives: usize) -> (f64, f64) {
+    random::seed(objectives as u64);
+    let benchmark = Benchmark::new(Dtlz::new(variant, objectives), POPULATION_SIZE);
+    let front = benchmark.problem.get_front(200);
+    let mut model = benchmark.get_model();
+    let mut operators = benchmark.get_operators();
+
+    evolve_with(&mut model, &mut operators).unwrap();
+    let before =
+        distance::inverted_generational_distance(&model.objectives, &model.archive, &front);
+    for _ in 0..GENERATIONS {
+        evolve_with(&mut model, &mut operators).unwrap();
+    }
+    let after = distance::inverted_generational_distance(&model.objectives, &model.archive, &front);
+
+    assert_eq!(model.archive.len(), POPULATION_SIZE);
+    model.archive.iter().for_each(|item| {
+        assert_eq!(item.values.len(), objectives);
+        assert!(item.values.iter().all(|value| value.is_finite()));
+    });
+    (before, after)
+}
+
+#[test]
+fn dtlz_three_objectives() {
+    [DtlzVariant::Dtlz1, DtlzVariant::Dtlz2, DtlzVariant::Dtlz7]
+        .iter()
+        .for_each(|&variant| {
+            let (before, after) = get_igd(variant, 3);
+            assert!(after < before, "{:?} {} - {}", variant, before, after);
+        });
+}
+
+#[test]
+fn dtlz_five_objectives() {
+    [DtlzVariant::Dtlz1, DtlzVariant::Dtlz2, DtlzVariant::Dtlz7]
+        .iter()
+        .for_each(|&variant| {
+            let (before, after) = get_igd(variant, 5);
+            assert!(after < before, "{:?} {} - {}", variant, before, after);
+        });
+}